@@ -17,7 +17,19 @@ pub enum Commands {
     /// Assemble an asm source file to a binary file. 
     Assemble(Assemble),
     /// Load and run a source file. 
-    Run(Run)
+    Run(Run),
+    /// Resume running from a snapshot file saved by the debugger. 
+    Resume(Resume),
+    /// Disassemble a binary file to an asm source file. 
+    Disassemble(Disassemble),
+    /// Assemble an asm source file and warn about likely mistakes in the program. 
+    Lint(Lint),
+    /// Rewrite an asm source file in the canonical layout. 
+    Fmt(Fmt),
+    /// Convert an asm source file between original and modern notation. 
+    Convert(Convert),
+    /// Link object files assembled with `--object` into a binary file. 
+    Link(Link),
 }
 
 #[derive(Args)]
@@ -33,6 +45,110 @@ pub struct Assemble {
     /// Use original notation for asm instructions. 
     #[arg(long, default_value_t = false)]
     pub og_notation: bool,
+
+    /// Write a listing of every store line, its word, instruction & source line to a file. 
+    #[arg(long)]
+    pub listing: Option<PathBuf>,
+
+    /// The format of the output file. 
+    #[arg(long, default_value_t = OutputFormat::Bin, value_enum)]
+    pub format: OutputFormat,
+
+    /// The byte order of each word in a binary output file. 
+    #[arg(long, default_value_t = Endianness::Big, value_enum)]
+    pub endian: Endianness,
+
+    /// Only write the store lines up to the last non-zero line to a binary output file, rather than the whole store. 
+    #[arg(long, default_value_t = false)]
+    pub partial: bool,
+
+    /// The program name stored in an image output file (defaults to the input file name). 
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// The address of the first instruction stored in an image output file. 
+    #[arg(long, default_value = "0", value_parser = parse_load_address)]
+    pub entry: usize,
+
+    /// The initial accumulator value stored in an image output file. 
+    #[arg(long, default_value = "0", value_parser = parse_word, allow_hyphen_values = true)]
+    pub init_acc: i32,
+
+    /// Assemble to a relocatable object file (defaults to input + .obj) to be linked with `ssemu link`. 
+    #[arg(long, default_value_t = false, conflicts_with_all = ["listing", "format", "endian", "partial", "name", "entry", "init_acc"])]
+    pub object: bool,
+}
+
+#[derive(Args)]
+pub struct Link {
+    /// The object files to link, placed in the store in the order given. 
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+
+    /// The output binary dump (defaults to the first input + .bin). 
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// The format of the output file. 
+    #[arg(long, default_value_t = OutputFormat::Bin, value_enum)]
+    pub format: OutputFormat,
+
+    /// The byte order of each word in a binary output file. 
+    #[arg(long, default_value_t = Endianness::Big, value_enum)]
+    pub endian: Endianness,
+}
+
+#[derive(Args)]
+pub struct Disassemble {
+    /// The input binary dump. 
+    #[arg(long, short)]
+    pub input: PathBuf,
+
+    /// The output asm file. 
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Use original notation for asm instructions. 
+    #[arg(long, default_value_t = false)]
+    pub og_notation: bool,
+}
+
+#[derive(Args)]
+pub struct Lint {
+    /// The input asm file. 
+    pub input: PathBuf,
+
+    /// Use original notation for asm instructions. 
+    #[arg(long, default_value_t = false)]
+    pub og_notation: bool,
+}
+
+#[derive(Args)]
+pub struct Fmt {
+    /// The asm file to format. 
+    pub input: PathBuf,
+
+    /// Use original notation for asm instructions. 
+    #[arg(long, default_value_t = false)]
+    pub og_notation: bool,
+
+    /// Don't write the file, fail if it isn't already formatted. 
+    #[arg(long, default_value_t = false)]
+    pub check: bool,
+}
+
+#[derive(Args)]
+pub struct Convert {
+    /// The asm file to convert. 
+    pub input: PathBuf,
+
+    /// The notation to convert to, the file is read in the other notation. 
+    #[arg(long, value_enum)]
+    pub to: Notation,
+
+    /// The output asm file (defaults to rewriting the input file). 
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
 }
 
 #[derive(Args, Clone)]
@@ -41,7 +157,7 @@ pub struct Run {
     pub src: PathBuf,
 
     /// The format of the file to execute from. 
-    #[arg(long, default_value_t = ExecuteFrom::Bin, value_enum)]
+    #[arg(long, default_value_t = ExecuteFrom::Auto, value_enum)]
     pub exe_from: ExecuteFrom,
 
     /// Use original notation for asm instructions if running from asm. 
@@ -67,14 +183,239 @@ pub struct Run {
     /// Addresses where to break & output the state of the core. 
     #[arg(long, value_parser = parse_breakpoint_addresses)]
     pub break_addr: Vec<usize>,
+
+    /// Maximum number of instructions to execute before halting with an error. 
+    #[arg(long)]
+    pub max_steps: Option<usize>,
+
+    /// Halt with an error when the whole model state repeats exactly (a provable infinite loop). 
+    #[arg(long, default_value_t = false)]
+    pub detect_loops: bool,
+
+    /// Record every executed instruction to a trace file. 
+    #[arg(long)]
+    pub trace: Option<PathBuf>,
+
+    /// The format of the trace file. 
+    #[arg(long, default_value_t = TraceFormat::Text, value_enum)]
+    pub trace_format: TraceFormat,
+
+    /// The number of previous states to keep for stepping back whilst debugging. 
+    #[arg(long, default_value_t = 1000)]
+    pub history: usize,
+
+    /// Count executions, reads & writes of each store line and output them when execution stops. 
+    #[arg(long, default_value_t = false)]
+    pub profile: bool,
+
+    /// Pace execution to the speed of the original machine (roughly 700 instructions per second) and output the emulated time when execution stops. 
+    #[arg(long, default_value_t = false)]
+    pub realtime: bool,
+
+    /// A multiplier on the speed of the original machine, implies `--realtime`. 
+    #[arg(long, value_parser = parse_speed)]
+    pub speed: Option<f64>,
+
+    /// Render the store, accumulator & control lines as the Williams tube display when execution stops or breakpoint encountered. 
+    #[arg(long, default_value_t = false)]
+    pub display: bool,
+
+    /// Debug in a full-screen terminal UI, starting paused before the first instruction. 
+    #[arg(long, default_value_t = false)]
+    pub tui: bool,
+
+    /// Allow binary images shorter than the store, the rest of the store is zeroed. 
+    #[arg(long, default_value_t = false)]
+    pub partial: bool,
+
+    /// The byte order of each word in binary images. 
+    #[arg(long, default_value_t = Endianness::Big, value_enum)]
+    pub endian: Endianness,
+
+    /// The store address to load a binary image at, implies `--partial`. 
+    #[arg(long, default_value = "0", value_parser = parse_load_address)]
+    pub load_addr: usize,
+
+    /// Binary images to overlay onto the store after loading the source, as `file` or `file@address`. 
+    #[arg(long, value_parser = parse_overlay)]
+    pub overlay: Vec<Overlay>,
+
+    /// Set the accumulator before execution starts. 
+    #[arg(long, value_parser = parse_word, allow_hyphen_values = true)]
+    pub init_acc: Option<i32>,
+
+    /// The address of the first instruction to execute. 
+    #[arg(long, value_parser = parse_load_address)]
+    pub entry: Option<usize>,
+
+    /// Set a store word before execution starts, as `address=value`, can be repeated. 
+    #[arg(long, value_parser = parse_poke, allow_hyphen_values = true)]
+    pub poke: Vec<Poke>,
+}
+
+#[derive(Args, Clone)]
+pub struct Resume {
+    /// The snapshot file to resume from. 
+    pub snapshot: PathBuf,
+
+    /// Output whole `model` including registers & memory when execution stops or breakpoint encountered. 
+    #[arg(long, default_value_t = false)]
+    pub output_model: bool,
+    
+    /// Go into a debug session when an error is encountered, as opposed to exiting. 
+    #[arg(long, default_value_t = false)]
+    pub debug_on_err: bool,
+
+    /// Memory addresses to output when execution stops or breakpoint encountered. 
+    #[arg(long, value_parser = parse_output_addresses)]
+    pub output_addr: Vec<usize>,
+
+    /// Registers to output when execution stops or breakpoint encountered. 
+    #[arg(long, value_enum)]
+    pub output_regs: Vec<Registers>,
+
+    /// Addresses where to break & output the state of the core. 
+    #[arg(long, value_parser = parse_breakpoint_addresses)]
+    pub break_addr: Vec<usize>,
+
+    /// Maximum number of instructions to execute before halting with an error, counting those executed before the snapshot was saved. 
+    #[arg(long)]
+    pub max_steps: Option<usize>,
+
+    /// Halt with an error when the whole model state repeats exactly (a provable infinite loop). 
+    #[arg(long, default_value_t = false)]
+    pub detect_loops: bool,
+
+    /// Record every executed instruction to a trace file. 
+    #[arg(long)]
+    pub trace: Option<PathBuf>,
+
+    /// The format of the trace file. 
+    #[arg(long, default_value_t = TraceFormat::Text, value_enum)]
+    pub trace_format: TraceFormat,
+
+    /// The number of previous states to keep for stepping back whilst debugging. 
+    #[arg(long, default_value_t = 1000)]
+    pub history: usize,
+
+    /// Count executions, reads & writes of each store line and output them when execution stops. 
+    #[arg(long, default_value_t = false)]
+    pub profile: bool,
+
+    /// Pace execution to the speed of the original machine (roughly 700 instructions per second) and output the emulated time when execution stops. 
+    #[arg(long, default_value_t = false)]
+    pub realtime: bool,
+
+    /// A multiplier on the speed of the original machine, implies `--realtime`. 
+    #[arg(long, value_parser = parse_speed)]
+    pub speed: Option<f64>,
+
+    /// Render the store, accumulator & control lines as the Williams tube display when execution stops or breakpoint encountered. 
+    #[arg(long, default_value_t = false)]
+    pub display: bool,
+
+    /// Debug in a full-screen terminal UI, starting paused before the next instruction. 
+    #[arg(long, default_value_t = false)]
+    pub tui: bool,
+}
+
+impl Resume {
+    /// Gets the run configuration to resume with, the options that only apply 
+    /// to loading a source take their defaults. 
+    pub fn run_conf(&self) -> Run {
+        Run {
+            src: self.snapshot.clone(),
+            exe_from: ExecuteFrom::Auto,
+            og_notation: false,
+            output_model: self.output_model,
+            debug_on_err: self.debug_on_err,
+            output_addr: self.output_addr.clone(),
+            output_regs: self.output_regs.clone(),
+            break_addr: self.break_addr.clone(),
+            max_steps: self.max_steps,
+            detect_loops: self.detect_loops,
+            trace: self.trace.clone(),
+            trace_format: self.trace_format,
+            history: self.history,
+            profile: self.profile,
+            realtime: self.realtime,
+            speed: self.speed,
+            display: self.display,
+            tui: self.tui,
+            partial: false,
+            endian: Endianness::Big,
+            load_addr: 0,
+            overlay: vec![],
+            init_acc: None,
+            entry: None,
+            poke: vec![],
+        }
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum ExecuteFrom {
+    /// Detects the format from the file extension, then the file contents. 
+    Auto,
     /// Executes from an assembly file. 
     Asm,
     /// Executes from a binary dump. 
     Bin,
+    /// Executes from a `.snp` file, each store line as 32 binary digits (lsb first). 
+    Snp,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Notation {
+    /// The original notation, e.g. `-X, C` & `Stop`. 
+    Og,
+    /// The modern notation, e.g. `ldn X` & `stp`. 
+    Modern,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum OutputFormat {
+    /// A binary dump, 4 bytes per store line (msb first). 
+    Bin,
+    /// A `.snp` file, each store line as 32 binary digits (lsb first). 
+    Snp,
+    /// An image with a header holding the program name, notation, entry point & 
+    /// initial accumulator, and a checksum. 
+    Image,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Endianness {
+    /// The most significant byte of each word first. 
+    Big,
+    /// The least significant byte of each word first. 
+    Little,
+}
+
+/// A binary image to be loaded over the store at an address. 
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overlay {
+    /// The binary image. 
+    pub path: PathBuf,
+    /// The store address to load the image at. 
+    pub addr: usize,
+}
+
+/// A store word to be set before execution starts. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Poke {
+    /// The store address to set. 
+    pub addr: usize,
+    /// The value to set it to. 
+    pub value: i32,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum TraceFormat {
+    /// Human readable text, one line per step. 
+    Text,
+    /// JSON Lines, one object per step. 
+    Json,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, EnumIter)]
@@ -85,6 +426,8 @@ pub enum Registers {
     Instruction,
     /// The instruction address register. 
     InstructionAddress,
+    /// A pseudo-register counting the machine beats executed so far. 
+    Beats,
 }
 
 pub fn parse_output_addresses(input: &str) -> Result<usize, String> {
@@ -105,12 +448,53 @@ fn parse_breakpoint_addresses(input: &str) -> Result<usize, String> {
     Ok(res)
 }
 
+fn parse_speed(input: &str) -> Result<f64, String> {
+    match input.parse::<f64>() {
+        Ok(v) if v.is_finite() && v > 0.0 => Ok(v),
+        _ => Err(format!("Invalid value passed as speed `{input}`, expected a positive number. "))
+    }
+}
+
+fn parse_load_address(input: &str) -> Result<usize, String> {
+    match parse_memory_address(input) {
+        Ok(v) if v < MEMORY_WORDS => Ok(v),
+        _ => Err(format!("Invalid value passed as load address `{input}`, Baby memory address space is {MEMORY_WORDS}. "))
+    }
+}
+
+pub fn parse_word(input: &str) -> Result<i32, String> {
+    let (negative, value) = match input.strip_prefix("-") {
+        Some(v) => (true, v),
+        None => (false, input),
+    };
+    let res = parse_memory_address(value).ok()
+        .filter(|v| *v <= u32::MAX as usize)
+        .ok_or(format!("Invalid value passed as a word `{input}`, expected a 32 bit number. "))? as u32 as i32;
+    Ok(if negative { res.wrapping_neg() } else { res })
+}
+
+pub fn parse_poke(input: &str) -> Result<Poke, String> {
+    let (addr, value) = input.split_once("=")
+        .ok_or(format!("Invalid poke `{input}`, expected `address=value`. "))?;
+    let addr = parse_load_address(addr.trim())
+        .map_err(|_| format!("Invalid poke address `{}`, Baby memory address space is {MEMORY_WORDS}. ", addr.trim()))?;
+    Ok(Poke { addr, value: parse_word(value.trim())? })
+}
+
+pub fn parse_overlay(input: &str) -> Result<Overlay, String> {
+    match input.rsplit_once("@") {
+        Some((path, addr)) if parse_memory_address(addr).is_ok() =>
+            Ok(Overlay { path: PathBuf::from(path), addr: parse_load_address(addr)? }),
+        _ => Ok(Overlay { path: PathBuf::from(input), addr: 0 }),
+    }
+}
+
 fn parse_memory_address(value: &str) -> Result<usize, ParseIntError> {
     let parse_res = match value {
         v if v.starts_with("0x") => usize::from_str_radix(&v.replace("0x", ""), 16),
         v if v.starts_with("0o") => usize::from_str_radix(&v.replace("0o", ""), 8),
         v if v.starts_with("0b") => usize::from_str_radix(&v.replace("0b", ""), 2),
-        v => usize::from_str_radix(&v.replace("0d", ""), 10),
+        v => v.replace("0d", "").parse::<usize>(),
     }?;
     Ok(parse_res)
 }
@@ -0,0 +1,349 @@
+use std::path::{Path, PathBuf};
+use baby_emulator::assembler::assemble;
+use baby_emulator::assembler::parser::{parse_line, strip_comments, LineType, Value};
+use baby_emulator::assembler::parser::errors::{
+    LineParseError, InstructionError, AbsoluteError, TagError, ValueParseError
+};
+use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
+use super::preprocess::{Preprocessed, Origin};
+
+
+/// The modern notation mnemonics, suggested for unknown instructions, those 
+/// taking an operand first. 
+pub const MNEMONICS: [&str; 7] = ["jmp", "jrp", "ldn", "sto", "sub", "cmp", "stp"];
+
+/// The furthest an unknown mnemonic or tag can be from a known one to be suggested. 
+pub const SUGGESTION_DISTANCE: usize = 2;
+
+/// An error found in an asm source, pointing at the offending part of a line. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    /// The line number in the source file, starting from 1. 
+    pub line: usize,
+    /// The column the offending text starts at, starting from 1. 
+    pub column: usize,
+    /// The length of the offending text. 
+    pub len: usize,
+    /// The error message. 
+    pub message: String,
+    /// A suggestion to fix the error, if any. 
+    pub help: Option<String>,
+}
+
+/// Calculates the edit distance between two strings. 
+/// 
+/// # Parameters 
+/// * `a` - The first string. 
+/// * `b` - The second string. 
+/// 
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            row.push((prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+/// Finds the closest of some candidates to a value, if any are close enough to suggest. 
+/// 
+/// # Parameters 
+/// * `value` - The value to be matched. 
+/// * `candidates` - The possible suggestions. 
+/// 
+pub fn closest<'a>(value: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|v| (edit_distance(&value.to_lowercase(), &v.to_lowercase()), v))
+        .filter(|(d, _)| *d <= SUGGESTION_DISTANCE)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, v)| v)
+}
+
+/// Finds the whitespace/comma delimited token of a line containing some text, 
+/// ignoring ASCII case, returning its column (from 1) and length, both in characters. 
+/// 
+/// Falls back to the whole line, trimmed, if the text isn't found. 
+/// 
+/// # Parameters 
+/// * `line` - The line to be searched. 
+/// * `needle` - The text to be found. 
+/// 
+pub fn locate(line: &str, needle: &str) -> (usize, usize) {
+    let code: Vec<char> = strip_comments(line).chars().collect();
+    let needle: Vec<char> = needle.chars().collect();
+    let is_delimiter = |c: &char| c.is_whitespace() || *c == ',';
+    let found = if needle.is_empty() { None } 
+        else { code.windows(needle.len()).position(|w| w.iter().zip(&needle).all(|(a, b)| a.eq_ignore_ascii_case(b))) };
+    match found {
+        Some(at) => {
+            let start = code[..at].iter().rposition(is_delimiter).map_or(0, |v| v + 1);
+            let after = at + needle.len();
+            let end = code[after..].iter().position(is_delimiter).map_or(code.len(), |v| v + after);
+            (start + 1, end - start)
+        },
+        None => {
+            let start = code.iter().take_while(|c| c.is_whitespace()).count();
+            let len = code.iter().rev().skip_while(|c| c.is_whitespace()).count();
+            (start + 1, len.saturating_sub(start).max(1))
+        },
+    }
+}
+
+/// Describes why a value couldn't be parsed, with a suggestion if possible. 
+/// 
+/// # Parameters 
+/// * `err` - The value error. 
+/// 
+pub fn describe_value_error(err: &ValueParseError) -> (String, String, Option<String>) {
+    match err {
+        ValueParseError::InvalidValue(v) => (
+            v.clone(),
+            format!("`{}` isn't a value", v),
+            match v.parse::<i32>() {
+                Ok(n) => Some(format!("values need a prefix, try `0d{}`", n)),
+                Err(_) => Some("values are written `0d10`, `0x0A`, `0o12`, `0b1010` or `$tag`".to_string()),
+            }
+        ),
+        ValueParseError::InvalidHex(v) => (
+            v.clone(),
+            format!("`0x{}` isn't a valid hex value", v),
+            u32::from_str_radix(v, 16).ok()
+                .map(|n| format!("values must fit in a signed 32 bit number, try `0d{}`", n as i32))
+        ),
+        ValueParseError::InvalidDecimal(v) => (v.clone(), format!("`0d{}` isn't a valid decimal value", v), None),
+        ValueParseError::InvalidOctal(v) => (v.clone(), format!("`0o{}` isn't a valid octal value", v), None),
+        ValueParseError::InvalidBinary(v) => (v.clone(), format!("`0b{}` isn't a valid binary value", v), None),
+        ValueParseError::InvalidTagName(v) => (
+            v.clone(),
+            format!("`${}` isn't a valid tag name", v),
+            Some("tag names can't contain whitespace".to_string())
+        ),
+    }
+}
+
+/// Suggests a fix for an unknown instruction, switching notation if the line 
+/// is recognised in the other notation, otherwise a close mnemonic. 
+/// 
+/// # Parameters 
+/// * `raw` - The source line. 
+/// * `mnemonic` - The unknown mnemonic. 
+/// * `og_notation` - Whether the source is being parsed with original notation. 
+/// 
+fn suggest_instruction(raw: &str, mnemonic: &str, og_notation: bool) -> Option<String> {
+    let other = parse_line(&raw.to_owned(), !og_notation);
+    if !matches!(other, Err(LineParseError::InstructionError(InstructionError::UnkownInstruction(_)))) {
+        return Some(if og_notation { "this is modern notation, try without --og-notation".to_string() }
+            else { "this is original notation, try --og-notation".to_string() });
+    }
+    if og_notation { return None; }
+    let lower = mnemonic.to_lowercase();
+    if MNEMONICS[..5].contains(&lower.as_str()) {
+        return Some(format!("`{}` needs an operand, e.g. `{} $tag`", lower.to_uppercase(), lower));
+    }
+    closest(mnemonic, MNEMONICS.into_iter())
+        .map(|v| format!("did you mean `{}`?", v.to_uppercase()))
+}
+
+/// Creates a diagnostic for an error parsing a line. 
+/// 
+/// # Parameters 
+/// * `line` - The line number, starting from 1. 
+/// * `raw` - The source line. 
+/// * `err` - The parse error. 
+/// * `og_notation` - Whether the source is being parsed with original notation. 
+/// 
+pub fn diagnose_line(line: usize, raw: &str, err: &LineParseError, og_notation: bool) -> Diagnostic {
+    let code = strip_comments(raw);
+    let (needle, message, help) = match err {
+        LineParseError::InstructionError(InstructionError::UnkownInstruction(_)) => {
+            let mnemonic = if og_notation { code.trim() }
+                else { code.trim().split(|c: char| c.is_whitespace() || c == ',').next().unwrap_or("") };
+            (
+                mnemonic.to_owned(),
+                format!("unknown instruction `{}`", mnemonic),
+                suggest_instruction(raw, mnemonic, og_notation)
+            )
+        },
+        LineParseError::InstructionError(InstructionError::OperandValueParseError(i, v)) => {
+            let (needle, message, help) = describe_value_error(v);
+            (needle, format!("invalid operand for {}, {}", i.describe(), message), help)
+        },
+        LineParseError::AbsoluteError(AbsoluteError::ValueError(v)) => {
+            let (needle, message, help) = describe_value_error(v);
+            (needle, format!("invalid absolute value, {}", message), help)
+        },
+        LineParseError::TagError(TagError::TagNameWhitespace(v)) => (
+            v.clone(),
+            format!("the tag name `{}` contains whitespace", v),
+            Some(format!("try `{}`", v.split_whitespace().collect::<Vec<&str>>().join("_")))
+        ),
+    };
+    let (column, len) = match err {
+        LineParseError::TagError(_) => {
+            let start = code.find(":").map_or(0, |v| v + 1);
+            let name = code[start..].trim_start();
+            (start + code[start..].len() - name.len() + 1, name.trim_end().len().max(1))
+        },
+        _ => locate(raw, &needle),
+    };
+    Diagnostic { line, column, len, message, help }
+}
+
+/// Gets the tag referenced by an instruction or absolute value line, if any. 
+/// 
+/// # Parameters 
+/// * `line` - The parsed line. 
+/// 
+fn referenced_tag(line: &LineType) -> Option<String> {
+    let value = match line {
+        LineType::Instruction(i) => i.get_operand(),
+        LineType::Absolute(v) => v.clone(),
+        LineType::Tag(_) => return None,
+    };
+    match value {
+        Value::Tag(t) => Some(t),
+        Value::Value(_) => None,
+    }
+}
+
+/// Suggests a declared tag for an unknown tag reference. 
+/// 
+/// Modern notation instructions are lowercased when parsed, so a reference 
+/// to a tag declared with capitals can never match. 
+/// 
+/// # Parameters 
+/// * `tag` - The unknown tag reference. 
+/// * `declared` - The declared tags. 
+/// * `og_notation` - Whether the source is being parsed with original notation. 
+/// 
+fn suggest_tag(tag: &str, declared: &[&str], og_notation: bool) -> Option<String> {
+    let found = closest(tag, declared.iter().copied())?;
+    if !og_notation && found.to_lowercase() == tag {
+        return Some(format!("instructions are lowercased in modern notation, declare the tag as `:{}`", tag));
+    }
+    Some(format!("did you mean `${}`?", found))
+}
+
+/// Checks an asm source for every error, rather than stopping at the first. 
+/// 
+/// Each line is parsed, then every tag reference is checked against the 
+/// declared tags and the program is checked to fit in the store. 
+/// 
+/// # Parameters 
+/// * `asm` - The asm source. 
+/// * `og_notation` - Whether the source uses original notation. 
+/// 
+pub fn diagnose(asm: &str, og_notation: bool) -> Vec<Diagnostic> {
+    let mut res = vec![];
+    let mut parsed = vec![];
+    for (i, raw) in asm.lines().enumerate() {
+        if strip_comments(raw).trim().is_empty() { continue; }
+        match parse_line(&raw.to_owned(), og_notation) {
+            Ok(l) => parsed.push((i + 1, raw, l)),
+            Err(e) => res.push(diagnose_line(i + 1, raw, &e, og_notation)),
+        }
+    }
+
+    let declared: Vec<&str> = parsed.iter()
+        .filter_map(|(_, _, l)| match l { LineType::Tag(t) => Some(t.as_str()), _ => None })
+        .collect();
+    for (line, raw, l) in &parsed {
+        let tag = match referenced_tag(l) {
+            Some(t) if !declared.contains(&t.as_str()) => t,
+            _ => continue,
+        };
+        let (column, len) = locate(raw, &format!("${}", tag));
+        res.push(Diagnostic {
+            line: *line, column, len,
+            message: format!("the tag `{}` is not declared", tag),
+            help: suggest_tag(&tag, &declared, og_notation),
+        });
+    }
+
+    let words: Vec<&(usize, &str, LineType)> = parsed.iter()
+        .filter(|(_, _, l)| !matches!(l, LineType::Tag(_)))
+        .collect();
+    if let Some((line, raw, _)) = words.get(MEMORY_WORDS) {
+        let (column, len) = locate(raw, "");
+        res.push(Diagnostic {
+            line: *line, column, len,
+            message: format!("the program is {} words long, the store is only {} words", words.len(), MEMORY_WORDS),
+            help: Some("this is the first line that doesn't fit".to_string()),
+        });
+    }
+
+    res.sort_by_key(|v| (v.line, v.column));
+    res
+}
+
+/// Renders diagnostics compiler-style, each with the file, line & column, the 
+/// source line with the offending text underlined and any suggestion. 
+/// 
+/// # Parameters 
+/// * `source` - The path of the asm source. 
+/// * `asm` - The asm source. 
+/// * `diagnostics` - The diagnostics to be rendered. 
+/// 
+pub fn render_diagnostics(source: &Path, asm: &str, diagnostics: &[Diagnostic]) -> String {
+    render_mapped(&Preprocessed::plain(source, asm), diagnostics)
+}
+
+/// Renders diagnostics found in a preprocessed source compiler-style, each 
+/// pointing at the file & line the offending line came from, noting the macro 
+/// it was expanded from if any. 
+/// 
+/// # Parameters 
+/// * `source` - The preprocessed source the diagnostics were found in. 
+/// * `diagnostics` - The diagnostics to be rendered. 
+/// 
+pub fn render_mapped(source: &Preprocessed, diagnostics: &[Diagnostic]) -> String {
+    let origin = |d: &Diagnostic| source.origins.get(d.line - 1).cloned()
+        .unwrap_or(Origin { path: PathBuf::new(), line: d.line, expansion: None });
+    let width = diagnostics.iter().map(|v| origin(v).line.to_string().len()).max().unwrap_or(1);
+    let gutter = " ".repeat(width);
+    let mut res = vec![];
+    for d in diagnostics {
+        let at = origin(d);
+        let text = source.lines.get(d.line - 1).map_or("", |v| v.as_str()).trim_end();
+        res.push(format!("error: {}", d.message));
+        res.push(format!("{}--> {}:{}:{}", gutter, at.path.to_string_lossy(), at.line, d.column));
+        res.push(format!("{} |", gutter));
+        res.push(format!("{:>width$} | {}", at.line, text, width = width));
+        res.push(format!("{} | {}{}", gutter, " ".repeat(d.column - 1), "^".repeat(d.len)));
+        if let Some(n) = &at.expansion {
+            res.push(format!("{} = note: {}", gutter, n));
+        }
+        if let Some(h) = &d.help {
+            res.push(format!("{} = help: {}", gutter, h));
+        }
+        res.push(String::new());
+    }
+    let count = diagnostics.len();
+    res.push(format!("{} error{} found. ", count, if count == 1 { "" } else { "s" }));
+    res.join("\n")
+}
+
+/// Assembles a preprocessed asm source, reporting every error compiler-style 
+/// at its original source location if it fails. 
+/// 
+/// # Parameters 
+/// * `source` - The preprocessed asm source. 
+/// * `og_notation` - Whether the source uses original notation. 
+/// 
+/// # Returns 
+/// * [Ok(Vec<BabyInstruction>)] - The assembled instructions. 
+/// * [Err(String)] - The rendered diagnostics. 
+/// 
+pub fn assemble_source(source: &Preprocessed, og_notation: bool) -> Result<Vec<BabyInstruction>, String> {
+    let asm = source.text();
+    assemble(&asm, og_notation).map_err(|e| {
+        let diagnostics = diagnose(&asm, og_notation);
+        if diagnostics.is_empty() { e.describe(true) }
+        else { render_mapped(source, &diagnostics) }
+    })
+}
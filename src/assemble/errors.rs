@@ -1,11 +1,13 @@
 use std::path::PathBuf;
-use baby_emulator::assembler::errors::AssemblyError;
+use crate::errors::{EXIT_FILE_ERROR, EXIT_ASSEMBLER_ERROR, EXIT_NOT_FORMATTED};
 
 
 /// Defines common behaviour for errors thrown during assembling. 
 pub trait AsmError {
     /// Gets a helper string describing an error. 
     fn describe(&self) -> String;
+    /// Gets the process exit code to report the error with. 
+    fn exit_code(&self) -> i32;
 }
 
 /// Possible errors when accessing a file. 
@@ -20,26 +22,41 @@ impl AsmError for SrcFileErrors {
     fn describe(&self) -> String {
         match self {
             SrcFileErrors::CouldntOpenFile(s) => 
-                format!("Couldn't open asm source file: `{}`.", s.to_string_lossy().to_string()),
+                format!("Couldn't open asm source file: `{}`.", s.to_string_lossy()),
             SrcFileErrors::CouldNotWriteToFile(s) => 
-                format!("Couldn't write assembled data to file: `{}`.", s.to_string_lossy().to_string()),
+                format!("Couldn't write assembled data to file: `{}`.", s.to_string_lossy()),
         }
     }
+
+    fn exit_code(&self) -> i32 {
+        EXIT_FILE_ERROR
+    }
 }
 
 /// Possible errors thrown during assembling a source file. 
 pub enum AsmErrors {
     /// Error accessing a file. 
     SrcFileError(SrcFileErrors),
-    /// Error assembling. 
-    AssembleError(AssemblyError)
+    /// Error assembling, with every error found in the source rendered. 
+    AssembleError(String),
+    /// A source file isn't in the canonical layout. 
+    NotFormatted(PathBuf),
 }
 
 impl AsmError for AsmErrors {
     fn describe(&self) -> String {
         match self {
-            AsmErrors::AssembleError(s) => format!("Problem assembling file. \n{}", s.describe(true)),
+            AsmErrors::AssembleError(s) => format!("Problem assembling file. \n{}", s),
             AsmErrors::SrcFileError(s) => format!("Issue accessing a file. \n{}", s.describe()),
+            AsmErrors::NotFormatted(s) => format!("`{}` isn't formatted, run `ssemu fmt` to format it. ", s.to_string_lossy()),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            AsmErrors::AssembleError(_) => EXIT_ASSEMBLER_ERROR,
+            AsmErrors::SrcFileError(s) => s.exit_code(),
+            AsmErrors::NotFormatted(_) => EXIT_NOT_FORMATTED,
         }
     }
 }
@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+use super::diagnostics::assemble_source;
+use super::preprocess::{Preprocessed, read_source};
+use baby_emulator::assembler::parser::{parse_line, strip_comments, LineType};
+use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
+use crate::interface::Interface;
+use crate::notation::describe_instruction;
+use super::errors::{AsmErrors, SrcFileErrors};
+
+
+/// A line of asm source that produced a store word. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct SourceLine {
+    /// The line number in the source file, starting from 1. 
+    pub line: usize,
+    /// The text of the line, trimmed. 
+    pub text: String,
+    /// The tag bound to the store word, if any. 
+    pub tag: Option<String>,
+    /// The included file or macro expansion the line came from, if any. 
+    pub origin: Option<String>,
+}
+
+/// Maps each store word to the source line that produced it. 
+/// 
+/// The nth line that's an instruction or `abs` value produces the nth 
+/// store word, tags are bound to the value line directly after them. 
+/// 
+/// # Parameters 
+/// * `asm` - The asm source. 
+/// * `og_notation` - Whether the source uses original notation. 
+/// 
+pub fn map_source(asm: &str, og_notation: bool) -> Vec<SourceLine> {
+    let mut res = vec![];
+    let mut tag = None;
+    for (i, line) in asm.lines().enumerate() {
+        if strip_comments(line).trim().is_empty() { continue; }
+        match parse_line(&line.to_owned(), og_notation) {
+            Ok(LineType::Tag(t)) => tag = Some(t),
+            Ok(_) => res.push(SourceLine { line: i + 1, text: line.trim().to_owned(), tag: tag.take(), origin: None }),
+            Err(_) => tag = None,
+        }
+    }
+    res
+}
+
+/// Maps each store word to the source line that produced it, in the file it 
+/// came from, for a preprocessed source. 
+/// 
+/// # Parameters 
+/// * `source` - The preprocessed asm source. 
+/// * `main` - The path of the source file that was preprocessed. 
+/// * `og_notation` - Whether the source uses original notation. 
+/// 
+pub fn map_preprocessed(source: &Preprocessed, main: &PathBuf, og_notation: bool) -> Vec<SourceLine> {
+    map_source(&source.text(), og_notation).into_iter()
+        .map(|v| {
+            let at = &source.origins[v.line - 1];
+            let mut notes = vec![];
+            if at.path != *main { notes.push(format!("in {}", at.path.to_string_lossy())); }
+            notes.extend(at.expansion.clone());
+            let origin = if notes.is_empty() { None } else { Some(notes.join(", ")) };
+            SourceLine { line: at.line, origin, ..v }
+        })
+        .collect()
+}
+
+/// Formats a word as binary, least significant bit first as the Baby stored it. 
+/// 
+/// # Parameters 
+/// * `word` - The word to be formatted. 
+/// 
+pub fn baby_bits(word: i32) -> String {
+    (0..32).map(|i| if (word >> i) & 1 == 1 { '1' } else { '0' }).collect()
+}
+
+/// Builds a listing of every store line, its encoded word, decoded instruction 
+/// and the source line that produced it. 
+/// 
+/// # Parameters 
+/// * `instructions` - The assembled instructions. 
+/// * `sources` - The source lines that produced each instruction. 
+/// 
+pub fn build_listing(instructions: &[BabyInstruction], sources: &[SourceLine]) -> Vec<String> {
+    let mut res = vec![format!(
+        "{:<6} {:<10} {:<32} {:<14} {:<16} {}", 
+        "Addr", "Word", "Bits (lsb first)", "Instruction", "Tag", "Source"
+    )];
+    for i in 0..MEMORY_WORDS {
+        let instruction = instructions.get(i).cloned().unwrap_or(BabyInstruction::AbsoluteValue(0));
+        let word = instruction.to_number();
+        let (tag, source) = match sources.get(i) {
+            Some(s) => (s.tag.clone().unwrap_or(String::new()), match &s.origin {
+                Some(o) => format!("{:>4}  {}  ({})", s.line, s.text, o),
+                None => format!("{:>4}  {}", s.line, s.text),
+            }),
+            None => (String::new(), "   -  (unused)".to_string()),
+        };
+        res.push(format!(
+            "{:<6} {:<10} {:<32} {:<14} {:<16} {}", 
+            format!("{:#04x}", i), format!("{:#010x}", word), baby_bits(word), 
+            describe_instruction(&instruction), tag, source
+        ).trim_end().to_owned());
+    }
+    res
+}
+
+/// Reads an asm source from an interface, builds a listing of it and writes it 
+/// to an interface. 
+/// 
+/// # Parameters 
+/// * `source` - The source path to the asm file. 
+/// * `og_notation` - Whether to use original notation for the assembling. 
+/// * `out` - The path to write the listing to. 
+/// * `interface` - The interface to read from and write to. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The listing was written. 
+/// * [Err(AsmErrors)] - There was an error reading the asm source, assembling or writing. 
+/// 
+pub fn write_listing(
+    source: &PathBuf, 
+    og_notation: bool, 
+    out: &Path, 
+    interface: &impl Interface
+) -> Result<(), AsmErrors> {
+    let pre = read_source(source, interface)?;
+
+    let instructions = assemble_source(&pre, og_notation)
+        .map_err(AsmErrors::AssembleError)?;
+
+    let mut listing = build_listing(&instructions, &map_preprocessed(&pre, source, og_notation)).join("\n");
+    listing.push('\n');
+
+    interface.write_fs_bytes(listing.into_bytes(), out)
+        .map_err(|_| AsmErrors::SrcFileError(SrcFileErrors::CouldNotWriteToFile(out.to_path_buf())))
+}
@@ -1,14 +1,29 @@
-use std::path::PathBuf;
-use baby_emulator::assembler::assemble as asm;
+use std::path::{Path, PathBuf};
 use baby_emulator::core::instructions::BabyInstruction;
-use crate::args::Assemble;
+use crate::args::{Assemble, OutputFormat, Endianness};
 use crate::errors::Errors;
 use crate::interface::Interface;
 use crate::run::ProgramStack;
+use crate::symbols::{Symbols, symbol_path};
+use crate::snp::serialise_snp;
+use crate::image::Image;
+use crate::object::OBJECT_EXTENSION;
 use errors::{AsmErrors, SrcFileErrors};
+use listing::write_listing;
+use diagnostics::assemble_source;
+use preprocess::read_source;
+use object::assemble_object;
 
 /// Possible error thrown during assembling. 
 pub mod errors;
+/// Contains helpers for writing a listing of an assembled program. 
+pub mod listing;
+/// Contains helpers for reporting every error in an asm source compiler-style. 
+pub mod diagnostics;
+/// Contains the preprocessor expanding includes, constants & macros. 
+pub mod preprocess;
+/// Contains helpers for assembling relocatable objects. 
+pub mod object;
 #[cfg(test)]
 mod tests;
 
@@ -25,28 +40,33 @@ mod tests;
 /// * [Err(AsmErrors)] - There was an error reading the asm source or assembling. 
 /// 
 pub fn get_src_from_asm(
-    source: &PathBuf, 
+    source: &Path, 
     og_notation: bool,
     interface: &impl Interface
 ) -> Result<ProgramStack, AsmErrors> {
-    let a = interface.read_fs_string(source)
-        .map_err(|_| AsmErrors::SrcFileError(SrcFileErrors::CouldntOpenFile(source.clone())))?;
+    let pre = read_source(source, interface)?;
 
-    let res = asm(&a, og_notation)
-        .map_err(|e| AsmErrors::AssembleError(e))?;
+    let res = assemble_source(&pre, og_notation)
+        .map_err(AsmErrors::AssembleError)?;
 
     Ok(BabyInstruction::to_numbers(res))
 }
 
 /// Takes an i32 word and bitshifts it into 4 i8s allowing storage in a file. 
 /// 
-/// Such that the lsb is at the back of the i8 vec and msb is at the front. 
+/// Big endian puts the lsb at the back of the i8 vec and msb at the front, 
+/// little endian the reverse. 
 /// 
 /// # Parameters 
 /// * `word` - The value to be formatted. 
+/// * `endian` - The byte order. 
 /// 
-pub fn format_word(word: i32) -> Vec<u8> {
-    (0..4).map(|i| (word >> ((3 - i) * 8)) as u8).collect()
+pub fn format_word(word: i32, endian: Endianness) -> Vec<u8> {
+    let res = (0..4).map(|i| (word >> ((3 - i) * 8)) as u8);
+    match endian {
+        Endianness::Big => res.collect(),
+        Endianness::Little => res.rev().collect(),
+    }
 }
 
 /// Takes an array of i38s, formats each one into 4 i8s, and flatmaps 
@@ -54,12 +74,83 @@ pub fn format_word(word: i32) -> Vec<u8> {
 /// 
 /// # Parameters 
 /// * `data` - The data to be formatted. 
+/// * `endian` - The byte order of each word. 
+/// * `partial` - Whether to leave out the trailing zero words. 
 /// 
-pub fn format_data(data: Vec<i32>) -> Vec<u8> {
-    data.into_iter().flat_map(format_word).collect()
+pub fn format_data(data: Vec<i32>, endian: Endianness, partial: bool) -> Vec<u8> {
+    let len = if partial { data.iter().rposition(|v| *v != 0).map_or(0, |v| v + 1) } 
+        else { data.len() };
+    data.into_iter().take(len).flat_map(|v| format_word(v, endian)).collect()
 }
 
-/// Formats the program stack as bytes and writes it to an interface. 
+/// Gets the extension of output files in a format. 
+/// 
+/// # Parameters 
+/// * `format` - The output format. 
+/// 
+pub fn format_extension(format: OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Bin => "bin",
+        OutputFormat::Snp => "snp",
+        OutputFormat::Image => "img",
+    }
+}
+
+/// Gets the path the output file is written to, the configured output or 
+/// the input + `.bin`/`.snp`/`.img` depending on the format, or `.obj` for 
+/// an object. 
+/// 
+/// # Parameters 
+/// * `conf` - The configration to be used. 
+/// 
+pub fn output_path(conf: &Assemble) -> PathBuf {
+    let extension = if conf.object { OBJECT_EXTENSION } else { format_extension(conf.format) };
+    match &conf.output {
+        Some(v) => v.clone(),
+        None => PathBuf::from(format!("{}.{}", conf.input.to_string_lossy(), extension))
+    }
+}
+
+/// Reads a source asm from an interface, assembles it into a relocatable 
+/// object and writes it to an interface. 
+/// 
+/// # Parameters 
+/// * `conf` - The configration to be used. 
+/// * `interface` - The interface to read from and write to. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The object was assembled and written. 
+/// * [Err(AsmErrors)] - There was an error reading the asm source, assembling or writing. 
+/// 
+pub fn write_object(conf: &Assemble, interface: &impl Interface) -> Result<(), AsmErrors> {
+    let pre = read_source(&conf.input, interface)?;
+    let object = assemble_object(&pre, conf.og_notation)
+        .map_err(AsmErrors::AssembleError)?;
+
+    let out = output_path(conf);
+    interface.write_fs_bytes(object.serialise().into_bytes(), &out)
+        .map_err(|_| AsmErrors::SrcFileError(SrcFileErrors::CouldNotWriteToFile(out.clone())))
+}
+
+/// Builds an image of the program stack with the configured metadata & initial state. 
+/// 
+/// # Parameters 
+/// * `data` - The program stack. 
+/// * `conf` - The configration to be used. 
+/// 
+pub fn build_image(data: ProgramStack, conf: &Assemble) -> Image {
+    let name = conf.name.clone().unwrap_or(
+        conf.input.file_stem().map(|v| v.to_string_lossy().to_string()).unwrap_or_default()
+    );
+    Image {
+        og_notation: conf.og_notation,
+        instruction_address: conf.entry as u16,
+        accumulator: conf.init_acc,
+        ..Image::new(&name, data)
+    }
+}
+
+/// Formats the program stack in the configured format and writes it to an interface. 
 /// 
 /// # Parameters 
 /// * `data` - The data to be formatted and written. 
@@ -75,12 +166,13 @@ pub fn write_to_file(
     conf: &Assemble,
     interface: &impl Interface
 ) -> Result<(), AsmErrors> {
-    let out = match &conf.output {
-        Some(v) => v.clone(),
-        None => PathBuf::from(conf.input.to_string_lossy().to_string() + ".bin")
-    };
+    let out = output_path(conf);
 
-    let d = format_data(Vec::from(data));
+    let d = match conf.format {
+        OutputFormat::Bin => format_data(Vec::from(data), conf.endian, conf.partial),
+        OutputFormat::Snp => serialise_snp(&data).into_bytes(),
+        OutputFormat::Image => build_image(data, conf).serialise(),
+    };
 
     interface.write_fs_bytes(d, &out)
         .map_err(|_| AsmErrors::SrcFileError(SrcFileErrors::CouldNotWriteToFile(out.clone())))?;
@@ -88,6 +180,28 @@ pub fn write_to_file(
     Ok(())
 }
 
+/// Writes the tag names of an asm source to a symbol file next to the binary file, 
+/// nothing is written if the source has no tags. 
+/// 
+/// # Parameters 
+/// * `conf` - The configration to be used. 
+/// * `interface` - The interface to read from and write to. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The symbol file was written or there were no tags. 
+/// * [Err(AsmErrors)] - There was an error reading the asm source or writing. 
+/// 
+pub fn write_symbols(conf: &Assemble, interface: &impl Interface) -> Result<(), AsmErrors> {
+    let pre = read_source(&conf.input, interface)?;
+
+    let symbols = Symbols::from_asm(&pre.text(), conf.og_notation);
+    if symbols.is_empty() { return Ok(()); }
+
+    let out = symbol_path(&output_path(conf));
+    interface.write_fs_bytes(symbols.serialise().into_bytes(), &out)
+        .map_err(|_| AsmErrors::SrcFileError(SrcFileErrors::CouldNotWriteToFile(out.clone())))
+}
+
 /// Attempts to read an asm string from an interface, assemble it, and write it back
 /// to an interface. 
 /// 
@@ -100,11 +214,24 @@ pub fn write_to_file(
 /// * [Err(Errors)] - An error was encountered during assembling/writing. 
 /// 
 pub fn assemble(conf: Assemble, interface: &impl Interface) -> Result<(), Errors> {
-    let bin = get_src_from_asm(&conf.input, conf.og_notation, interface)
-        .map_err(|e| Errors::AsmError(e))?;
+    if conf.object {
+        write_object(&conf, interface)
+            .map_err(Errors::AsmError)?;
+    } else {
+        let bin = get_src_from_asm(&conf.input, conf.og_notation, interface)
+            .map_err(Errors::AsmError)?;
+
+        write_to_file(bin, &conf, interface)
+            .map_err(Errors::AsmError)?;
+
+        write_symbols(&conf, interface)
+            .map_err(Errors::AsmError)?;
+    }
 
-    write_to_file(bin, &conf, interface)
-        .map_err(|e| Errors::AsmError(e))?;
+    if let Some(out) = &conf.listing {
+        write_listing(&conf.input, conf.og_notation, out, interface)
+            .map_err(Errors::AsmError)?;
+    }
 
     Ok(())
 }
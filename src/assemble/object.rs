@@ -0,0 +1,124 @@
+use baby_emulator::assembler::parser::{parse_line, parse_asm_string, strip_comments, LineType, Value};
+use baby_emulator::assembler::linker::{inline_tags, position_tags, UnlinkedData};
+use baby_emulator::assembler::linker::errors::TagError;
+use crate::object::{Object, Reference, Field};
+use super::diagnostics::{Diagnostic, diagnose, closest, render_mapped};
+use super::preprocess::{Preprocessed, Declaration};
+
+
+/// Records an error found on an `%export` or `%import` line. 
+/// 
+/// The directive line is appended to the source the errors are rendered from, 
+/// as it isn't part of the expanded source. 
+/// 
+/// # Parameters 
+/// * `report` - The source the errors are rendered from. 
+/// * `diagnostics` - The errors found so far. 
+/// * `declaration` - The offending declaration. 
+/// * `message` - The error message. 
+/// * `help` - A suggestion to fix the error, if any. 
+/// 
+fn declaration_error(
+    report: &mut Preprocessed,
+    diagnostics: &mut Vec<Diagnostic>,
+    declaration: &Declaration,
+    message: String,
+    help: Option<String>
+) {
+    let code = strip_comments(&declaration.line);
+    let column = code.rfind(&declaration.name).map_or(1, |v| code[..v].chars().count() + 1);
+    let len = declaration.name.chars().count();
+    report.lines.push(declaration.line.clone());
+    report.origins.push(declaration.origin.clone());
+    diagnostics.push(Diagnostic { line: report.lines.len(), column, len, message, help });
+}
+
+/// Gets the tag referenced by a word and the part of the word holding it, if any. 
+/// 
+/// # Parameters 
+/// * `data` - The unlinked word. 
+/// 
+fn reference_of(data: &UnlinkedData) -> Option<(String, Field)> {
+    match data {
+        UnlinkedData::Absolute(Value::Tag(t)) => Some((t.clone(), Field::Word)),
+        UnlinkedData::Instruction(i) => match i.get_operand() {
+            Value::Tag(t) => Some((t, Field::Operand)),
+            Value::Value(_) => None,
+        },
+        UnlinkedData::Absolute(Value::Value(_)) => None,
+    }
+}
+
+/// Assembles a preprocessed asm source into a relocatable object, reporting 
+/// every error compiler-style at its original source location if it fails. 
+/// 
+/// Tags declared with `%import` are left to be resolved when linking, every 
+/// tag declared with `%export` must label a store line in the source. 
+/// 
+/// # Parameters 
+/// * `source` - The preprocessed asm source. 
+/// * `og_notation` - Whether the source uses original notation. 
+/// 
+/// # Returns 
+/// * [Ok(Object)] - The assembled object. 
+/// * [Err(String)] - The rendered diagnostics. 
+/// 
+pub fn assemble_object(source: &Preprocessed, og_notation: bool) -> Result<Object, String> {
+    let asm = source.text();
+    let imports: Vec<&str> = source.imports.iter().map(|v| v.name.as_str()).collect();
+    let declared: Vec<String> = asm.lines()
+        .filter_map(|v| match parse_line(&v.to_owned(), og_notation) {
+            Ok(LineType::Tag(t)) => Some(t),
+            _ => None,
+        })
+        .collect();
+
+    let mut with_imports = asm.clone();
+    imports.iter().for_each(|v| with_imports += &format!("\n:{}", v));
+    let mut report = source.clone();
+    let mut diagnostics = diagnose(&with_imports, og_notation);
+
+    for import in &source.imports {
+        if declared.contains(&import.name) {
+            declaration_error(&mut report, &mut diagnostics, import,
+                format!("the tag `{}` is imported but also declared in this file", import.name),
+                Some("remove the `%import` to use the tag declared here".to_string()));
+        }
+    }
+    for export in &source.exports {
+        if imports.contains(&export.name.as_str()) {
+            declaration_error(&mut report, &mut diagnostics, export,
+                format!("the tag `{}` is imported, it can't be exported", export.name), None);
+        } else if !declared.contains(&export.name) {
+            let candidates: Vec<&str> = declared.iter().map(|v| v.as_str()).collect();
+            declaration_error(&mut report, &mut diagnostics, export,
+                format!("the tag `{}` is exported but not declared", export.name),
+                closest(&export.name, candidates.into_iter()).map(|v| format!("did you mean `{}`?", v)));
+        }
+    }
+    if !diagnostics.is_empty() { return Err(render_mapped(&report, &diagnostics)); }
+
+    let inlined = inline_tags(parse_asm_string(&asm, og_notation)
+        .map_err(|(l, _)| format!("Couldn't parse line {}. ", l))?);
+    let positions = position_tags(&inlined);
+    let mut tags = positions.clone();
+    imports.iter().for_each(|v| { tags.insert(v.to_string(), 0); });
+    let unlabelled = |t: &str| format!("The tag `{}` doesn't label a store line. ", t);
+
+    let mut res = Object::default();
+    for (i, (_, data)) in inlined.iter().enumerate() {
+        let word = data.resolve(&tags)
+            .map_err(|e| match e { TagError::UnknownTagName(t) => unlabelled(&t) })?;
+        res.words.push(word.to_number());
+        if let Some((tag, field)) = reference_of(data) {
+            let symbol = if imports.contains(&tag.as_str()) { Some(tag) } else { None };
+            res.references.push(Reference { addr: i, field, symbol });
+        }
+    }
+    for export in &source.exports {
+        let addr = positions.get(&export.name).ok_or(unlabelled(&export.name))?;
+        res.exports.push((export.name.clone(), *addr as usize));
+    }
+    res.imports = imports.iter().map(|v| v.to_string()).collect();
+    Ok(res)
+}
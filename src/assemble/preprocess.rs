@@ -0,0 +1,502 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf, Component};
+use baby_emulator::assembler::parser::strip_comments;
+use crate::interface::Interface;
+use crate::stdlib::{ROUTINES, routine, routine_path};
+use super::diagnostics::{Diagnostic, locate, render_mapped, closest};
+use super::errors::{AsmErrors, SrcFileErrors};
+
+
+/// The most macro expansions in a source, stops macros that invoke each other 
+/// many times over from expanding exponentially. 
+pub const MAX_EXPANSIONS: usize = 1024;
+
+/// Where a line of preprocessed source came from. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Origin {
+    /// The source file the line is in. 
+    pub path: PathBuf,
+    /// The line number in the source file, starting from 1. 
+    pub line: usize,
+    /// The macro invocation the line was expanded from, if any. 
+    pub expansion: Option<String>,
+}
+
+/// A tag name declared with `%export` or `%import`. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Declaration {
+    /// The tag name. 
+    pub name: String,
+    /// The directive line. 
+    pub line: String,
+    /// Where the directive came from. 
+    pub origin: Origin,
+}
+
+/// An asm source with the preprocessor directives expanded, and where each 
+/// line came from. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Preprocessed {
+    /// The expanded source lines. 
+    pub lines: Vec<String>,
+    /// Where each expanded line came from. 
+    pub origins: Vec<Origin>,
+    /// The tags exported with `%export`, for other objects to link against. 
+    pub exports: Vec<Declaration>,
+    /// The tags imported with `%import`, to be resolved when linking. 
+    pub imports: Vec<Declaration>,
+}
+
+impl Preprocessed {
+    /// Creates a preprocessed source from a source with no directives, each 
+    /// line coming from the same line of the file. 
+    /// 
+    /// # Parameters 
+    /// * `path` - The path of the source file. 
+    /// * `asm` - The asm source. 
+    /// 
+    pub fn plain(path: &Path, asm: &str) -> Preprocessed {
+        let lines: Vec<String> = asm.lines().map(|v| v.to_owned()).collect();
+        let origins = (0..lines.len())
+            .map(|i| Origin { path: path.to_path_buf(), line: i + 1, expansion: None })
+            .collect();
+        Preprocessed { lines, origins, exports: vec![], imports: vec![] }
+    }
+
+    /// Gets the expanded source as a string. 
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// A macro defined with `%macro`. 
+#[derive(Clone, PartialEq, Debug)]
+struct Macro {
+    /// The parameter names. 
+    params: Vec<String>,
+    /// The body lines and where they came from. 
+    body: Vec<(String, Origin)>,
+}
+
+/// The state of the preprocessor whilst expanding a source. 
+struct Preprocessor<'a, I: Interface> {
+    /// The interface to read included files from. 
+    interface: &'a I,
+    /// The constants defined with `%define`. 
+    defines: HashMap<String, String>,
+    /// The macros defined with `%macro`. 
+    macros: HashMap<String, Macro>,
+    /// The number of macro expansions so far, used to make local labels unique. 
+    expansions: usize,
+    /// The macros currently being expanded, to detect macros invoking themselves. 
+    expanding: Vec<String>,
+    /// The files currently being included, to detect include cycles. 
+    including: Vec<PathBuf>,
+    /// The expanded source. 
+    res: Preprocessed,
+    /// The errors found and the source lines they were found on. 
+    errors: Preprocessed,
+    /// The errors found, each on the line of the same index in `errors`. 
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Determines if a character can be part of a constant, macro or parameter name. 
+/// 
+/// # Parameters 
+/// * `c` - The character. 
+/// 
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Replaces every whole word of a line's code that's a key in a map with its 
+/// value, ignoring comments and tag names (words directly after `$` or `:`). 
+/// 
+/// # Parameters 
+/// * `line` - The source line. 
+/// * `names` - The words to be replaced and their replacements. 
+/// 
+pub fn substitute(line: &str, names: &HashMap<String, String>) -> String {
+    if names.is_empty() { return line.to_owned(); }
+    let (code, comment) = match line.split_once(";") {
+        Some((c, v)) => (c, Some(v)),
+        None => (line, None),
+    };
+    let mut res = String::new();
+    let mut word = String::new();
+    let mut prev = ' ';
+    let mut before_word = ' ';
+    let flush = |word: &mut String, res: &mut String, before: char| {
+        match names.get(word.as_str()) {
+            Some(v) if before != '$' && before != ':' => res.push_str(v),
+            _ => res.push_str(word),
+        }
+        word.clear();
+    };
+    for c in code.chars() {
+        if is_name_char(c) {
+            if word.is_empty() { before_word = prev; }
+            word.push(c);
+        } else {
+            flush(&mut word, &mut res, before_word);
+            res.push(c);
+        }
+        prev = c;
+    }
+    flush(&mut word, &mut res, before_word);
+    if let Some(c) = comment { res.push(';'); res.push_str(c); }
+    res
+}
+
+/// Renames the local labels of a macro body line (`:.name` & `$.name`) so 
+/// they are unique to one expansion of the macro. 
+/// 
+/// # Parameters 
+/// * `line` - The macro body line. 
+/// * `prefix` - The prefix unique to the expansion. 
+/// 
+pub fn localise(line: &str, prefix: &str) -> String {
+    let (code, comment) = match line.split_once(";") {
+        Some((c, v)) => (c, Some(v)),
+        None => (line, None),
+    };
+    let mut res = code.replace("$.", &format!("${}_", prefix));
+    if res.trim_start().starts_with(":.") {
+        res = res.replacen(":.", &format!(":{}_", prefix), 1);
+    }
+    if let Some(c) = comment { res.push(';'); res.push_str(c); }
+    res
+}
+
+/// Gets the names defined by the preprocessor directives in a source, the 
+/// constants, macros and macro parameters. 
+/// 
+/// # Parameters 
+/// * `asm` - The asm source. 
+/// 
+pub fn defined_names(asm: &str) -> Vec<String> {
+    let mut res = vec![];
+    for line in asm.lines() {
+        let code = strip_comments(line);
+        if let Some(v) = code.trim().strip_prefix("%define ") {
+            res.extend(v.split_whitespace().take(1).map(|v| v.to_owned()));
+        } else if let Some(v) = code.trim().strip_prefix("%macro ") {
+            res.extend(v.split([' ', '\t', ',']).filter(|v| !v.is_empty()).map(|v| v.to_owned()));
+        }
+    }
+    res
+}
+
+/// Replaces every whole word of a line's code that's one of some names with a 
+/// placeholder value, so a line using constants or macro parameters can be parsed. 
+/// 
+/// # Parameters 
+/// * `line` - The source line. 
+/// * `names` - The names to be replaced. 
+/// 
+pub fn mask_names(line: &str, names: &[String]) -> String {
+    substitute(line, &names.iter().map(|v| (v.clone(), "0d0".to_string())).collect())
+}
+
+/// Determines if a line is a preprocessor directive. 
+/// 
+/// # Parameters 
+/// * `line` - The source line. 
+/// 
+pub fn is_directive(line: &str) -> bool {
+    line.trim_start().starts_with("%")
+}
+
+/// Determines if a line is an invocation of one of some macros. 
+/// 
+/// # Parameters 
+/// * `line` - The source line. 
+/// * `names` - The macro names. 
+/// 
+pub fn is_invocation(line: &str, names: &[String]) -> bool {
+    strip_comments(line).split_whitespace().next()
+        .is_some_and(|v| names.iter().any(|n| n == v))
+}
+
+/// Removes `.` & `..` components from a path without touching the filesystem, 
+/// so the same file included by different relative paths is recognised. 
+/// 
+/// # Parameters 
+/// * `path` - The path to be normalised. 
+/// 
+pub fn normalise(path: &Path) -> PathBuf {
+    let mut res = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir if matches!(res.components().next_back(), Some(Component::Normal(_))) => { res.pop(); },
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+impl<'a, I: Interface> Preprocessor<'a, I> {
+    /// Records an error found on a source line. 
+    /// 
+    /// # Parameters 
+    /// * `raw` - The source line. 
+    /// * `origin` - Where the line came from. 
+    /// * `needle` - The offending text on the line, to be underlined. 
+    /// * `message` - The error message. 
+    /// * `help` - A suggestion to fix the error, if any. 
+    /// 
+    fn error(&mut self, raw: &str, origin: &Origin, needle: &str, message: String, help: Option<String>) {
+        let (column, len) = locate(raw, needle);
+        self.errors.lines.push(raw.to_owned());
+        self.errors.origins.push(origin.clone());
+        self.diagnostics.push(Diagnostic { line: self.errors.lines.len(), column, len, message, help });
+    }
+
+    /// Reads and expands a source file. 
+    /// 
+    /// # Parameters 
+    /// * `path` - The path of the source file. 
+    /// * `asm` - The asm source. 
+    /// 
+    fn file(&mut self, path: &Path, asm: &str) {
+        self.including.push(path.to_path_buf());
+        let lines: Vec<(String, Origin)> = asm.lines().enumerate()
+            .map(|(i, v)| (v.to_owned(), Origin { path: path.to_path_buf(), line: i + 1, expansion: None }))
+            .collect();
+        self.lines(&lines);
+        self.including.pop();
+    }
+
+    /// Expands a run of source lines, collecting macro definitions. 
+    /// 
+    /// # Parameters 
+    /// * `lines` - The source lines and where they came from. 
+    /// 
+    fn lines(&mut self, lines: &[(String, Origin)]) {
+        let mut defining: Option<(String, Macro, &str, &Origin)> = None;
+        for (raw, origin) in lines {
+            let code = strip_comments(raw);
+            let mut words = code.split_whitespace();
+            let directive = words.next().filter(|v| v.starts_with("%"));
+
+            if let Some((name, mut body, start, start_origin)) = defining.take() {
+                match directive {
+                    Some("%endmacro") => { self.macros.insert(name, body); },
+                    Some("%macro") => {
+                        self.error(raw, origin, "%macro", "macros can't be defined inside a macro".to_string(),
+                            Some(format!("close `{}` with `%endmacro` first", name)));
+                        defining = Some((name, body, start, start_origin));
+                    },
+                    _ => {
+                        body.body.push((raw.clone(), origin.clone()));
+                        defining = Some((name, body, start, start_origin));
+                    },
+                }
+                continue;
+            }
+
+            match directive {
+                Some("%include") => self.include(raw, origin, code.trim()["%include".len()..].trim()),
+                Some("%define") => match (words.next(), words.next()) {
+                    (Some(name), Some(_)) => {
+                        let value = code.trim()["%define".len()..].trim()[name.len()..].trim();
+                        let value = substitute(value, &self.defines);
+                        self.defines.insert(name.to_owned(), value);
+                    },
+                    _ => self.error(raw, origin, "%define", "expected a name and a value".to_string(),
+                        Some("constants are defined as `%define NAME value`".to_string())),
+                },
+                Some("%macro") => {
+                    let rest = code.trim()["%macro".len()..].trim();
+                    let mut names = rest.split([' ', '\t', ',']).filter(|v| !v.is_empty()).map(|v| v.to_owned());
+                    match names.next() {
+                        Some(name) => defining = Some((name, Macro { params: names.collect(), body: vec![] }, raw, origin)),
+                        None => self.error(raw, origin, "%macro", "expected a macro name".to_string(),
+                            Some("macros are defined as `%macro NAME param, param`".to_string())),
+                    }
+                },
+                Some("%endmacro") => self.error(raw, origin, "%endmacro", "`%endmacro` without a `%macro`".to_string(), None),
+                Some(d @ ("%export" | "%import")) => match (words.next(), words.next()) {
+                    (Some(name), None) => {
+                        let declaration = Declaration { name: name.to_owned(), line: raw.clone(), origin: origin.clone() };
+                        if d == "%export" { self.res.exports.push(declaration) } 
+                        else { self.res.imports.push(declaration) }
+                    },
+                    _ => self.error(raw, origin, d, "expected a tag name".to_string(),
+                        Some(format!("tags are declared as `{} name`", d))),
+                },
+                Some(d) => self.error(raw, origin, d, format!("unknown directive `{}`", d),
+                    Some("the directives are `%include`, `%define`, `%macro`, `%endmacro`, `%export` & `%import`".to_string())),
+                None => {
+                    let line = substitute(raw, &self.defines);
+                    match strip_comments(&line).split_whitespace().next() {
+                        Some(name) if self.macros.contains_key(name) => self.expand(&line, origin, name),
+                        _ => {
+                            self.res.lines.push(line);
+                            self.res.origins.push(origin.clone());
+                        },
+                    }
+                },
+            }
+        }
+        if let Some((name, _, start, start_origin)) = defining {
+            self.error(start, start_origin, &name, format!("the macro `{}` is never closed", name),
+                Some("close it with `%endmacro`".to_string()));
+        }
+    }
+
+    /// Expands an included file in place. 
+    /// 
+    /// # Parameters 
+    /// * `raw` - The `%include` line. 
+    /// * `origin` - Where the line came from. 
+    /// * `file` - The file name as written, optionally in quotes, or a bundled 
+    ///   routine name in angle brackets. 
+    /// 
+    fn include(&mut self, raw: &str, origin: &Origin, file: &str) {
+        if let Some(name) = file.strip_prefix('<').and_then(|v| v.strip_suffix('>')) {
+            return self.include_routine(raw, origin, file, name.trim());
+        }
+        let name = file.trim_matches('"');
+        if name.is_empty() {
+            return self.error(raw, origin, "%include", "expected a file name".to_string(),
+                Some("files are included as `%include \"file.asm\"`".to_string()));
+        }
+        let path = normalise(&origin.path.parent().map_or(PathBuf::from(name), |v| v.join(name)));
+        if self.including.contains(&path) {
+            return self.error(raw, origin, file, format!("`{}` includes itself", path.to_string_lossy()), None);
+        }
+        match self.interface.read_fs_string(&path) {
+            Ok(v) => self.file(&path, &v),
+            Err(_) => self.error(raw, origin, file, format!("couldn't open `{}`", path.to_string_lossy()), None),
+        }
+    }
+
+    /// Expands a routine bundled with the standard library in place. 
+    /// 
+    /// # Parameters 
+    /// * `raw` - The `%include` line. 
+    /// * `origin` - Where the line came from. 
+    /// * `file` - The routine name as written, in angle brackets. 
+    /// * `name` - The routine name. 
+    /// 
+    fn include_routine(&mut self, raw: &str, origin: &Origin, file: &str, name: &str) {
+        let path = routine_path(name);
+        if self.including.contains(&path) {
+            return self.error(raw, origin, file, format!("`{}` includes itself", path.to_string_lossy()), None);
+        }
+        match routine(name) {
+            Some(v) => self.file(&path, v),
+            None => {
+                let help = match closest(name, ROUTINES.iter().map(|(n, _)| *n)) {
+                    Some(v) => format!("did you mean `<{}>`?", v),
+                    None => format!("the bundled routines are {}", ROUTINES.iter()
+                        .map(|(n, _)| format!("`{}`", n)).collect::<Vec<String>>().join(", ")),
+                };
+                self.error(raw, origin, file, format!("there's no bundled routine `{}`", name), Some(help))
+            },
+        }
+    }
+
+    /// Expands a macro invocation in place. 
+    /// 
+    /// # Parameters 
+    /// * `raw` - The invocation line, with constants substituted. 
+    /// * `origin` - Where the line came from. 
+    /// * `name` - The macro name. 
+    /// 
+    fn expand(&mut self, raw: &str, origin: &Origin, name: &str) {
+        let mac = self.macros[name].clone();
+        let code = strip_comments(raw);
+        let args: Vec<String> = code.trim()[name.len()..].split(",")
+            .map(|v| v.trim().to_owned())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if args.len() != mac.params.len() {
+            return self.error(raw, origin, name,
+                format!("the macro `{}` takes {} argument{}, {} given", name, mac.params.len(),
+                    if mac.params.len() == 1 { "" } else { "s" }, args.len()),
+                Some(format!("`{}` is invoked as `{} {}`", name, name, mac.params.join(", "))));
+        }
+        if self.expanding.iter().any(|v| v == name) {
+            return self.error(raw, origin, name, format!("the macro `{}` invokes itself", name),
+                Some("macros can't invoke themselves, directly or through another macro".to_string()));
+        }
+        // Carries on counting past the limit so it's only reported once. 
+        self.expansions += 1;
+        if self.expansions > MAX_EXPANSIONS {
+            if self.expansions == MAX_EXPANSIONS + 1 {
+                self.error(raw, origin, name, format!("macros are expanded more than {} times", MAX_EXPANSIONS),
+                    Some("a macro invoking others many times over expands exponentially".to_string()));
+            }
+            return;
+        }
+
+        let prefix = format!("{}_{}", name, self.expansions).to_lowercase();
+        let params: HashMap<String, String> = mac.params.into_iter().zip(args).collect();
+        let note = format!("in macro `{}` invoked at {}:{}", name, origin.path.to_string_lossy(), origin.line);
+        let body: Vec<(String, Origin)> = mac.body.iter()
+            .map(|(l, o)| (
+                localise(&substitute(l, &params), &prefix),
+                Origin { expansion: Some(note.clone()), ..o.clone() }
+            ))
+            .collect();
+        self.expanding.push(name.to_owned());
+        self.lines(&body);
+        self.expanding.pop();
+    }
+}
+
+/// Expands the preprocessor directives in an asm source. 
+/// 
+/// * `%include "file.asm"` - Expands another file in place, relative to the including file. 
+/// * `%include <name>` - Expands a routine bundled with the standard library in place. 
+/// * `%define NAME value` - Replaces the word `NAME` with `value` on the following lines. 
+/// * `%macro NAME param, param` ... `%endmacro` - Defines a macro, invoked as `NAME arg, arg`, 
+///   the parameters are replaced with the arguments, tags starting with `.` are local to 
+///   each expansion. 
+/// 
+/// # Parameters 
+/// * `path` - The path of the source file. 
+/// * `asm` - The asm source. 
+/// * `interface` - The interface to read included files from. 
+/// 
+/// # Returns 
+/// * [Ok(Preprocessed)] - The expanded source. 
+/// * [Err(String)] - Every error found, rendered. 
+/// 
+pub fn preprocess(path: &Path, asm: &str, interface: &impl Interface) -> Result<Preprocessed, String> {
+    let empty = Preprocessed { lines: vec![], origins: vec![], exports: vec![], imports: vec![] };
+    let mut state = Preprocessor {
+        interface,
+        defines: HashMap::new(),
+        macros: HashMap::new(),
+        expansions: 0,
+        expanding: vec![],
+        including: vec![],
+        res: empty.clone(),
+        errors: empty,
+        diagnostics: vec![],
+    };
+    state.file(path, asm);
+    if state.diagnostics.is_empty() { Ok(state.res) }
+    else { Err(render_mapped(&state.errors, &state.diagnostics)) }
+}
+
+/// Reads an asm source file from an interface and expands its preprocessor directives. 
+/// 
+/// # Parameters 
+/// * `source` - The path of the source file. 
+/// * `interface` - The interface to read from. 
+/// 
+/// # Returns 
+/// * [Ok(Preprocessed)] - The expanded source. 
+/// * [Err(AsmErrors)] - The file couldn't be read or preprocessed. 
+/// 
+pub fn read_source(source: &Path, interface: &impl Interface) -> Result<Preprocessed, AsmErrors> {
+    let asm = interface.read_fs_string(source)
+        .map_err(|_| AsmErrors::SrcFileError(SrcFileErrors::CouldntOpenFile(source.to_path_buf())))?;
+    preprocess(source, &asm, interface)
+        .map_err(AsmErrors::AssembleError)
+}
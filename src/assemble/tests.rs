@@ -1,18 +1,396 @@
 use super::*;
+use crate::test_utils::TestInterface;
+use baby_emulator::core::MEMORY_WORDS;
 
 
+fn default_assemble() -> Assemble {
+    Assemble {
+        input: PathBuf::from("foo.asm"),
+        output: None,
+        og_notation: false,
+        listing: None,
+        format: OutputFormat::Bin,
+        endian: Endianness::Big,
+        partial: false,
+        name: None,
+        entry: 0,
+        init_acc: 0,
+        object: false,
+    }
+}
+
 #[test]
 fn test_format_word() {
-    assert_eq!(format_word(0x0F0FF0F0), vec![0x0F, 0x0F, 0xF0, 0xF0])
+    assert_eq!(format_word(0x0F0FF0F0, Endianness::Big), vec![0x0F, 0x0F, 0xF0, 0xF0]);
+    assert_eq!(format_word(0x0F0FF0F0, Endianness::Little), vec![0xF0, 0xF0, 0x0F, 0x0F]);
 }
 
 #[test]
 fn test_format_data() {
     assert_eq!(
-        format_data(vec![0x0F0F_F0F0, 0x0F0F_FFF0]), 
+        format_data(vec![0x0F0F_F0F0, 0x0F0F_FFF0], Endianness::Big, false), 
         vec![
             0x0F, 0x0F, 0xF0, 0xF0,
             0x0F, 0x0F, 0xFF, 0xF0
         ]
-    )
+    );
+    assert_eq!(
+        format_data(vec![0x0F0F_F0F0, 0, 0x0F0F_FFF0, 0, 0], Endianness::Little, true), 
+        vec![
+            0xF0, 0xF0, 0x0F, 0x0F,
+            0, 0, 0, 0,
+            0xF0, 0xFF, 0x0F, 0x0F
+        ]
+    );
+    assert_eq!(format_data(vec![0, 0], Endianness::Big, true), vec![]);
+}
+
+const LISTING_ASM: &str = 
+"; Counts down from 10
+ldn $start_value
+
+:loop_start_value
+sub $subtract_val ; Subtract 1
+cmp
+jmp $loop_start
+stp
+:loop_start
+abs $loop_start_value
+:subtract_val
+abs 0d1
+:start_value
+abs 0d-10
+";
+
+#[test]
+fn test_map_source() {
+    let sources = listing::map_source(LISTING_ASM, false);
+    assert_eq!(sources.len(), 8);
+    assert_eq!(sources[0], listing::SourceLine { line: 2, text: "ldn $start_value".to_string(), tag: None, origin: None });
+    assert_eq!(sources[1], listing::SourceLine { 
+        line: 5, text: "sub $subtract_val ; Subtract 1".to_string(), tag: Some("loop_start_value".to_string()), origin: None 
+    });
+    assert_eq!(sources[7].line, 14);
+}
+
+#[test]
+fn test_baby_bits() {
+    assert_eq!(listing::baby_bits(0b1101), format!("1011{}", "0".repeat(28)));
+}
+
+#[test]
+fn test_build_listing() {
+    let asm = LISTING_ASM.to_owned();
+    let instructions = baby_emulator::assembler::assemble(&asm, false).ok().unwrap();
+    let res = listing::build_listing(&instructions, &listing::map_source(&asm, false));
+    assert_eq!(res.len(), 33);
+    assert_eq!(res[0], format!(
+        "{:<6} {:<10} {:<32} {:<14} {:<16} {}", "Addr", "Word", "Bits (lsb first)", "Instruction", "Tag", "Source"
+    ));
+    assert_eq!(res[2], format!(
+        "0x01   0x00002006 01100000000001000000000000000000 SUB 0x06       loop_start_value    5  sub $subtract_val ; Subtract 1"
+    ));
+    assert_eq!(res[8], format!(
+        "0x07   0xfffffff6 01101111111111111111111111111111 ABS -10        start_value        14  abs 0d-10"
+    ));
+    assert_eq!(res[9], format!(
+        "0x08   0x00000000 00000000000000000000000000000000 ABS 0                              -  (unused)"
+    ));
+}
+
+#[test]
+fn test_write_listing() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "stp".to_string());
+    test_int.should_write_addr = PathBuf::from("foo.lst");
+    test_int.should_write_data = (listing::build_listing(
+        &[BabyInstruction::Stop], 
+        &[listing::SourceLine { line: 1, text: "stp".to_string(), tag: None, origin: None }]
+    ).join("\n") + "\n").into_bytes();
+    assert!(listing::write_listing(&PathBuf::from("foo.asm"), false, &PathBuf::from("foo.lst"), &test_int).is_ok());
+}
+
+#[test]
+fn test_symbols_from_asm() {
+    let asm = ":start\nldn $counter\n; comment\n:loop\nsto $counter\nstp\n:counter\nabs 0d5".to_string();
+    let symbols = Symbols::from_asm(&asm, false);
+    assert_eq!(symbols.address_of("start"), Some(0));
+    assert_eq!(symbols.address_of("LOOP"), Some(1));
+    assert_eq!(symbols.address_of("counter"), Some(3));
+    assert_eq!(symbols.address_of("foo"), None);
+    assert_eq!(symbols.name_of(1), Some("loop"));
+    assert_eq!(symbols.name_of(2), None);
+    assert_eq!(symbols.serialise(), format!("0x00 start\n0x01 loop\n0x03 counter\n"));
+    assert_eq!(Symbols::parse(&symbols.serialise()), Ok(symbols));
+}
+
+#[test]
+fn test_parse_symbols() {
+    assert_eq!(Symbols::parse("  ; comment\n\n0x1f  end  ; the end\n").ok().unwrap().address_of("end"), Some(31));
+    assert!(Symbols::parse("0x20 foo").is_err());
+    assert!(Symbols::parse("0xZZ foo").is_err());
+    assert!(Symbols::parse("foo").is_err());
+}
+
+#[test]
+fn test_write_symbols() {
+    let conf = default_assemble();
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "stp\n:end\nabs 0d1".to_string());
+    test_int.should_write_addr = PathBuf::from("foo.asm.sym");
+    test_int.should_write_data = "0x01 end\n".to_string().into_bytes();
+    assert!(write_symbols(&conf, &test_int).is_ok());
+
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "stp".to_string());
+    assert!(write_symbols(&conf, &test_int).is_ok());
+}
+
+#[test]
+fn test_write_snp() {
+    let mut conf = default_assemble();
+    conf.format = OutputFormat::Snp;
+    let mut stack = [0; MEMORY_WORDS];
+    stack[0] = BabyInstruction::Stop.to_number();
+    stack[1] = -2;
+    let snp = serialise_snp(&stack);
+    let lines: Vec<&str> = snp.lines().collect();
+    assert_eq!(lines.len(), MEMORY_WORDS + 1);
+    assert_eq!(lines[0], "32");
+    assert_eq!(lines[1], "0000: 00000000000001110000000000000000 ; STP");
+    assert_eq!(lines[2], "0001: 01111111111111111111111111111111 ; STP");
+
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.should_write_addr = PathBuf::from("foo.asm.snp");
+    test_int.should_write_data = snp.into_bytes();
+    assert!(write_to_file(stack, &conf, &test_int).is_ok());
+}
+
+#[test]
+fn test_write_image() {
+    let mut conf = default_assemble();
+    conf.format = OutputFormat::Image;
+    conf.og_notation = true;
+    conf.entry = 2;
+    conf.init_acc = -5;
+    let mut stack = [0; MEMORY_WORDS];
+    stack[2] = BabyInstruction::Stop.to_number();
+    let image = build_image(stack, &conf);
+    assert_eq!(image.name, "foo");
+    assert_eq!(image.instruction_address, 2);
+    assert_eq!(image.accumulator, -5);
+    assert!(image.og_notation);
+
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.should_write_addr = PathBuf::from("foo.asm.img");
+    test_int.should_write_data = image.serialise();
+    assert!(write_to_file(stack, &conf, &test_int).is_ok());
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(diagnostics::edit_distance("lnd", "ldn"), 2);
+    assert_eq!(diagnostics::edit_distance("sto", "sto"), 0);
+    assert_eq!(diagnostics::edit_distance("", "stp"), 3);
+    assert_eq!(diagnostics::closest("LNX", diagnostics::MNEMONICS.into_iter()), Some("ldn"));
+    assert_eq!(diagnostics::closest("foobar", diagnostics::MNEMONICS.into_iter()), None);
+}
+
+#[test]
+fn test_diagnose() {
+    let asm = "; comment\nlnd $start\n\n  sub 0xzz ; bad\nabs 12\n:bad tag\n:start\njmp $strt\nstp".to_string();
+    let res = diagnostics::diagnose(&asm, false);
+    assert_eq!(res.len(), 5);
+    assert_eq!((res[0].line, res[0].column, res[0].len), (2, 1, 3));
+    assert_eq!(res[0].help, Some("did you mean `LDN`?".to_string()));
+    assert_eq!((res[1].line, res[1].column, res[1].len), (4, 7, 4));
+    assert_eq!((res[2].line, res[2].column, res[2].len), (5, 5, 2));
+    assert_eq!(res[2].help, Some("values need a prefix, try `0d12`".to_string()));
+    assert_eq!((res[3].line, res[3].column, res[3].len), (6, 2, 7));
+    assert_eq!(res[3].help, Some("try `bad_tag`".to_string()));
+    assert_eq!((res[4].line, res[4].column, res[4].len), (8, 5, 5));
+    assert_eq!(res[4].help, Some("did you mean `$start`?".to_string()));
+
+    assert!(diagnostics::diagnose(LISTING_ASM, false).is_empty());
+}
+
+#[test]
+fn test_diagnose_non_ascii() {
+    let res = diagnostics::diagnose("ldn $İİxyz", false);
+    assert_eq!((res[0].line, res[0].column, res[0].len), (1, 1, 10));
+    assert_eq!(diagnostics::locate("  ÄB sub $x", "$X"), (10, 2));
+    assert_eq!(diagnostics::locate("  éé ", "foo"), (3, 2));
+}
+
+#[test]
+fn test_diagnose_notation() {
+    let res = diagnostics::diagnose("X, Cl", false);
+    assert_eq!(res[0].help, Some("this is original notation, try --og-notation".to_string()));
+    let res = diagnostics::diagnose("Stop\nstp", true);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].help, Some("this is modern notation, try without --og-notation".to_string()));
+}
+
+#[test]
+fn test_diagnose_overflow() {
+    let asm = vec!["stp"; MEMORY_WORDS + 2].join("\n");
+    let res = diagnostics::diagnose(&asm, false);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].line, MEMORY_WORDS + 1);
+}
+
+#[test]
+fn test_render_diagnostics() {
+    let asm = "stp\nsub 0d1x".to_string();
+    let res = diagnostics::render_diagnostics(&PathBuf::from("foo.asm"), &asm, &diagnostics::diagnose(&asm, false));
+    assert_eq!(res, format!(
+        "error: invalid operand for subtract, `0d1x` isn't a valid decimal value\n \
+        --> foo.asm:2:5\n  |\n2 | sub 0d1x\n  |     ^^^^\n\n1 error found. "
+    ));
+}
+
+#[test]
+fn test_get_src_from_asm_diagnostics() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "ldn $a\nfoo\nbar".to_string());
+    match get_src_from_asm(&PathBuf::from("foo.asm"), false, &test_int) {
+        Err(AsmErrors::AssembleError(s)) => {
+            assert!(s.contains("foo.asm:1:5"));
+            assert!(s.contains("foo.asm:3:1"));
+            assert!(s.ends_with("3 errors found. "));
+        },
+        _ => panic!()
+    }
+}
+
+const MACROS_ASM: &str = "; Macros
+%macro load_pos value, tmp
+    ldn value
+    sto tmp
+    ldn tmp
+%endmacro
+%macro wait counter
+:.loop
+    sub counter
+    cmp
+    jmp $.ptr
+:.ptr
+    abs $.loop
+%endmacro
+";
+
+fn preprocess_test(asm: &str) -> Result<preprocess::Preprocessed, String> {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("lib/macros.asm"), MACROS_ASM.to_owned());
+    test_int.string_files.insert(PathBuf::from("lib/loop.asm"), "%include \"../foo.asm\"".to_string());
+    preprocess::preprocess(&PathBuf::from("foo.asm"), asm, &test_int)
+}
+
+#[test]
+fn test_substitute() {
+    let names = std::collections::HashMap::from([("X".to_string(), "0d5".to_string()), ("tmp".to_string(), "$t".to_string())]);
+    assert_eq!(preprocess::substitute("ldn X ; X", &names), "ldn 0d5 ; X");
+    assert_eq!(preprocess::substitute(":X", &names), ":X");
+    assert_eq!(preprocess::substitute("sto $tmp", &names), "sto $tmp");
+    assert_eq!(preprocess::substitute("sto tmp", &names), "sto $t");
+    assert_eq!(preprocess::substitute("sto tmp2", &names), "sto tmp2");
+    assert_eq!(preprocess::localise(":.loop ; :.", "wait_1"), ":wait_1_loop ; :.");
+    assert_eq!(preprocess::localise("jmp $.ptr", "wait_1"), "jmp $wait_1_ptr");
+    assert_eq!(preprocess::normalise(&PathBuf::from("lib/./../foo.asm")), PathBuf::from("foo.asm"));
+    assert_eq!(preprocess::normalise(&PathBuf::from("../foo.asm")), PathBuf::from("../foo.asm"));
+}
+
+#[test]
+fn test_preprocess() {
+    let res = preprocess_test("%include \"lib/macros.asm\"\n%define FIVE $five\n    load_pos FIVE, 0x1f ; hi\n    wait $one\n    wait $one\n:five\n    abs 0d5").ok().unwrap();
+    assert_eq!(res.lines, vec![
+        format!("; Macros"),
+        format!("    ldn $five"),
+        format!("    sto 0x1f"),
+        format!("    ldn 0x1f"),
+        format!(":wait_2_loop"),
+        format!("    sub $one"),
+        format!("    cmp"),
+        format!("    jmp $wait_2_ptr"),
+        format!(":wait_2_ptr"),
+        format!("    abs $wait_2_loop"),
+        format!(":wait_3_loop"),
+        format!("    sub $one"),
+        format!("    cmp"),
+        format!("    jmp $wait_3_ptr"),
+        format!(":wait_3_ptr"),
+        format!("    abs $wait_3_loop"),
+        format!(":five"),
+        format!("    abs 0d5"),
+    ]);
+    assert_eq!(res.origins[1], preprocess::Origin { 
+        path: PathBuf::from("lib/macros.asm"), line: 3, expansion: Some("in macro `load_pos` invoked at foo.asm:3".to_string()) 
+    });
+    assert_eq!(res.origins[17], preprocess::Origin { path: PathBuf::from("foo.asm"), line: 7, expansion: None });
+
+    let sources = listing::map_preprocessed(&res, &PathBuf::from("foo.asm"), false);
+    assert_eq!(sources[0].line, 3);
+    assert_eq!(sources[0].origin, Some("in lib/macros.asm, in macro `load_pos` invoked at foo.asm:3".to_string()));
+    assert_eq!(sources.last().unwrap().origin, None);
+}
+
+#[test]
+fn test_preprocess_errors() {
+    let res = preprocess_test("%include \"lib/loop.asm\"\n%foo\n%define X\n%endmacro\n%include \"nope.asm\"\n%macro m a\n%macro n\n%endmacro\nm\n%macro open").err().unwrap();
+    assert!(res.contains("error: `foo.asm` includes itself\n  --> lib/loop.asm:1:10"));
+    assert!(res.contains("error: unknown directive `%foo`\n  --> foo.asm:2:1"));
+    assert!(res.contains("error: expected a name and a value\n  --> foo.asm:3:1"));
+    assert!(res.contains("error: `%endmacro` without a `%macro`\n  --> foo.asm:4:1"));
+    assert!(res.contains("error: couldn't open `nope.asm`\n  --> foo.asm:5:10"));
+    assert!(res.contains("error: macros can't be defined inside a macro\n  --> foo.asm:7:1"));
+    assert!(res.contains("error: the macro `m` takes 1 argument, 0 given\n  --> foo.asm:9:1"));
+    assert!(res.contains("error: the macro `open` is never closed\n  --> foo.asm:10:8"));
+    assert!(res.ends_with("8 errors found. "));
+
+    let res = preprocess_test("%macro m\n    m\n%endmacro\nm").err().unwrap();
+    assert!(res.contains("error: the macro `m` invokes itself\n --> foo.asm:2:5"));
+    assert!(res.ends_with("1 error found. "));
+}
+
+#[test]
+fn test_preprocess_recursion() {
+    let res = preprocess_test("%macro m\n    m\n    m\n%endmacro\nm").err().unwrap();
+    assert!(res.contains("error: the macro `m` invokes itself"));
+    assert!(res.ends_with("2 errors found. "));
+
+    let res = preprocess_test("%macro a\n    b\n%endmacro\n%macro b\n    a\n%endmacro\na").err().unwrap();
+    assert!(res.contains("error: the macro `a` invokes itself\n --> foo.asm:5:5"));
+
+    let mut asm = "%macro m0\n    stp\n%endmacro\n".to_string();
+    (1..12).for_each(|i| asm += &format!("%macro m{i}\n    m{0}\n    m{0}\n%endmacro\n", i - 1));
+    asm += "m11";
+    let res = preprocess_test(&asm).err().unwrap();
+    assert!(res.contains(&format!("error: macros are expanded more than {} times", preprocess::MAX_EXPANSIONS)));
+    assert!(res.ends_with("1 error found. "));
+}
+
+#[test]
+fn test_get_src_from_asm_preprocessed() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "%macro m\n    ldn 0x1f\n    lnd 0x1f\n%endmacro\n    m".to_string());
+    match get_src_from_asm(&PathBuf::from("foo.asm"), false, &test_int) {
+        Err(AsmErrors::AssembleError(s)) => assert_eq!(s, format!(
+            "error: unknown instruction `lnd`\n --> foo.asm:3:5\n  |\n3 |     lnd 0x1f\n  |     ^^^\n  \
+            = note: in macro `m` invoked at foo.asm:5\n  = help: did you mean `LDN`?\n\n1 error found. "
+        )),
+        _ => panic!()
+    }
+
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "%define X 0d7\n%macro m a\n    ldn a\n%endmacro\n    m X\n    stp".to_string());
+    let res = get_src_from_asm(&PathBuf::from("foo.asm"), false, &test_int).ok().unwrap();
+    assert_eq!(res[0..3], [BabyInstruction::Negate(7).to_number(), BabyInstruction::Stop.to_number(), 0]);
+}
+
+#[test]
+fn test_assemble_object() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "%export start\n:start\n    ldn $start\n    stp".to_string());
+    test_int.should_write_addr = PathBuf::from("foo.asm.obj");
+    test_int.should_write_data = "ssemu-object 1\nword 16384\nword 57344\nexport 0x00 start\nref 0x00 operand\n".to_string().into_bytes();
+    assert!(assemble(Assemble { object: true, ..default_assemble() }, &test_int).is_ok());
 }
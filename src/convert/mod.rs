@@ -0,0 +1,135 @@
+use baby_emulator::assembler::parser::{parse_line, strip_comments, LineType, Instruction};
+use crate::args::{Convert, Notation};
+use crate::errors::Errors;
+use crate::interface::Interface;
+use crate::notation::write_instruction;
+use crate::formatter::to_baby_instruction;
+use crate::assemble::diagnostics::{Diagnostic, diagnose_line, render_diagnostics};
+use crate::assemble::errors::{AsmErrors, SrcFileErrors};
+use crate::assemble::preprocess::{defined_names, mask_names, is_directive, is_invocation};
+
+#[cfg(test)]
+mod tests;
+
+
+/// Gets the operand of an instruction exactly as written in the source. 
+/// 
+/// # Parameters 
+/// * `code` - The instruction, without any comment. 
+/// * `instruction` - The parsed instruction. 
+/// * `og_notation` - Whether the instruction is in original notation. 
+/// 
+pub fn operand_text<'a>(code: &'a str, instruction: &Instruction, og_notation: bool) -> &'a str {
+    let code = code.trim();
+    if !og_notation {
+        return code.split_once(char::is_whitespace).map_or("", |(_, o)| o.trim());
+    }
+    let res = match instruction {
+        Instruction::Jump(_) => code.strip_suffix(", Cl"),
+        Instruction::RelativeJump(_) => code.strip_prefix("Add ").and_then(|v| v.strip_suffix(", Cl")),
+        Instruction::Negate(_) => code.strip_prefix("-").and_then(|v| v.strip_suffix(", C")),
+        Instruction::Store(_) => code.strip_prefix("c, "),
+        Instruction::Subtract(_) => code.strip_prefix("SUB "),
+        Instruction::Test | Instruction::Stop => None,
+    };
+    res.unwrap_or("").trim()
+}
+
+/// Converts a source line to the other notation, keeping its indentation and 
+/// the column of any comment where possible. 
+/// 
+/// Only instruction lines change, tags, `abs` lines, comments and blank lines 
+/// are the same in both notations. 
+/// 
+/// # Parameters 
+/// * `raw` - The source line. 
+/// * `line` - The parsed line. 
+/// * `to_og` - Whether to convert to original notation, otherwise to modern. 
+/// 
+pub fn convert_line(raw: &str, line: &LineType, to_og: bool) -> String {
+    let instruction = match line {
+        LineType::Instruction(i) => i,
+        _ => return raw.to_owned(),
+    };
+    let code = strip_comments(raw);
+    let indent = &code[..code.len() - code.trim_start().len()];
+    let converted = write_instruction(
+        &to_baby_instruction(instruction),
+        operand_text(&code, instruction, !to_og),
+        to_og
+    );
+    match raw.split_once(";") {
+        Some((_, comment)) => {
+            let width = code.len().max(indent.len() + converted.len() + 1);
+            format!("{:<width$};{}", format!("{}{}", indent, converted), comment, width = width)
+        },
+        None => format!("{}{}", indent, converted),
+    }
+}
+
+/// Converts an asm source between original and modern notation, preserving 
+/// tags, comments and layout. 
+/// 
+/// Preprocessor directives and macro invocations are kept as they are, the 
+/// bodies of macros are converted. 
+/// 
+/// # Parameters 
+/// * `asm` - The asm source. 
+/// * `to_og` - Whether to convert to original notation, otherwise to modern. 
+/// 
+/// # Returns 
+/// * [Ok(String)] - The converted source. 
+/// * [Err(Vec<Diagnostic>)] - Lines of the source couldn't be parsed in the source notation. 
+/// 
+pub fn convert_source(asm: &str, to_og: bool) -> Result<String, Vec<Diagnostic>> {
+    let names = defined_names(asm);
+    let mut res = vec![];
+    let mut errors = vec![];
+    for (i, raw) in asm.lines().enumerate() {
+        if strip_comments(raw).trim().is_empty() || is_directive(raw) {
+            res.push(raw.to_owned());
+            continue;
+        }
+        let parsed = parse_line(&raw.to_owned(), !to_og)
+            .or_else(|e| parse_line(&mask_names(raw, &names), !to_og).map_err(|_| e));
+        match parsed {
+            Ok(l) => res.push(convert_line(raw, &l, to_og)),
+            Err(_) if is_invocation(raw, &names) => res.push(raw.to_owned()),
+            Err(e) => {
+                let mut diagnostic = diagnose_line(i + 1, raw, &e, !to_og);
+                if parse_line(&raw.to_owned(), to_og).is_ok() {
+                    let notation = if to_og { "original" } else { "modern" };
+                    diagnostic.help = Some(format!("this line is already in {} notation", notation));
+                }
+                errors.push(diagnostic);
+            },
+        }
+    }
+    if !errors.is_empty() { return Err(errors); }
+    let mut res = res.join("\n");
+    if asm.ends_with("\n") { res.push('\n'); }
+    Ok(res)
+}
+
+/// Reads an asm source file from an interface, converts it to the other 
+/// notation and writes it back to an interface. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to be used. 
+/// * `interface` - The interface to be used for reading & writing. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The file was converted & written. 
+/// * [Err(Errors)] - The file couldn't be read, parsed or written. 
+/// 
+pub fn convert(conf: Convert, interface: &impl Interface) -> Result<(), Errors> {
+    let asm = interface.read_fs_string(&conf.input)
+        .map_err(|_| Errors::AsmError(AsmErrors::SrcFileError(SrcFileErrors::CouldntOpenFile(conf.input.clone()))))?;
+
+    let converted = convert_source(&asm, conf.to == Notation::Og)
+        .map_err(|e| Errors::AsmError(AsmErrors::AssembleError(render_diagnostics(&conf.input, &asm, &e))))?;
+
+    let out = conf.output.unwrap_or(conf.input);
+    interface.write_fs_bytes(converted.into_bytes(), &out)
+        .map_err(|_| Errors::AsmError(AsmErrors::SrcFileError(SrcFileErrors::CouldNotWriteToFile(out.clone()))))
+}
@@ -0,0 +1,104 @@
+use std::path::PathBuf;
+use super::*;
+use baby_emulator::assembler::assemble;
+use crate::test_utils::TestInterface;
+
+
+const MODERN: &str = "; Counts down
+  ldn $start   ; load
+:loop
+  sub $one
+  cmp
+  jmp $ptr     ; back
+  jrp 0x1
+  sto 0d3
+
+  stp
+:ptr
+  abs $loop
+:one
+  abs 0d1
+:start
+  abs 0d3
+";
+
+const OG: &str = "; Counts down
+  -$start, C   ; load
+:loop
+  SUB $one
+  Test
+  $ptr, Cl     ; back
+  Add 0x1, Cl
+  c, 0d3
+
+  Stop
+:ptr
+  abs $loop
+:one
+  abs 0d1
+:start
+  abs 0d3
+";
+
+#[test]
+fn test_operand_text() {
+    let parse = |v: &str, og| match parse_line(&v.to_owned(), og) {
+        Ok(LineType::Instruction(i)) => i,
+        _ => panic!()
+    };
+    assert_eq!(operand_text("  ldn $Foo ", &parse("ldn $Foo", false), false), "$Foo");
+    assert_eq!(operand_text("Add 0x1, Cl", &parse("Add 0x1, Cl", true), true), "0x1");
+    assert_eq!(operand_text("-0d5, C", &parse("-0d5, C", true), true), "0d5");
+    assert_eq!(operand_text("Stop", &parse("Stop", true), true), "");
+}
+
+#[test]
+fn test_convert_line() {
+    let line = "  jmp $ptr     ; back";
+    let parsed = parse_line(&line.to_owned(), false).ok().unwrap();
+    assert_eq!(convert_line(line, &parsed, true), "  $ptr, Cl     ; back");
+    let line = "  sto $x ; long";
+    let parsed = parse_line(&line.to_owned(), false).ok().unwrap();
+    assert_eq!(convert_line(line, &parsed, true), "  c, $x  ; long");
+    let line = "  Add $x, Cl; long";
+    let parsed = parse_line(&line.to_owned(), true).ok().unwrap();
+    assert_eq!(convert_line(line, &parsed, false), "  jrp $x    ; long");
+}
+
+#[test]
+fn test_convert_source() {
+    assert_eq!(convert_source(MODERN, true).ok().unwrap(), OG);
+    assert_eq!(convert_source(OG, false).ok().unwrap(), MODERN);
+    assert_eq!(
+        assemble(&MODERN.to_owned(), false).ok().unwrap(),
+        assemble(&OG.to_owned(), true).ok().unwrap()
+    );
+    let res = convert_source(MODERN, false).err().unwrap();
+    assert_eq!(res.len(), 7);
+    assert_eq!(res[0].line, 2);
+    assert_eq!(res[0].help, Some("this line is already in modern notation".to_string()));
+}
+
+#[test]
+fn test_convert() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), MODERN.to_owned());
+    test_int.should_write_addr = PathBuf::from("og.asm");
+    test_int.should_write_data = OG.to_owned().into_bytes();
+    let conf = Convert { input: PathBuf::from("foo.asm"), to: Notation::Og, output: Some(PathBuf::from("og.asm")) };
+    assert!(convert(conf, &test_int).is_ok());
+
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), OG.to_owned());
+    test_int.should_write_addr = PathBuf::from("foo.asm");
+    test_int.should_write_data = MODERN.to_owned().into_bytes();
+    assert!(convert(Convert { input: PathBuf::from("foo.asm"), to: Notation::Modern, output: None }, &test_int).is_ok());
+}
+
+#[test]
+fn test_convert_source_directives() {
+    let asm = "%define X 0d3\n%macro neg a\n    ldn a ; load\n%endmacro\n    neg X\n    sub X\n".to_string();
+    assert_eq!(convert_source(&asm, true).ok().unwrap(), format!(
+        "%define X 0d3\n%macro neg a\n    -a, C ; load\n%endmacro\n    neg X\n    SUB X\n"
+    ));
+}
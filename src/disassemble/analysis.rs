@@ -0,0 +1,140 @@
+use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
+use crate::notation::has_operand;
+use crate::run::ProgramStack;
+
+
+/// Decodes a word as an instruction, only if it's exactly the encoding of 
+/// that instruction so writing it as an instruction won't change the word. 
+/// 
+/// # Parameters 
+/// * `word` - The word to be decoded. 
+/// 
+pub fn decode(word: i32) -> Option<BabyInstruction> {
+    let instruction = BabyInstruction::from_number(word as u16);
+    if instruction.to_number() == word { Some(instruction) } else { None }
+}
+
+/// Gets the address a jump instruction at an address will go to. 
+/// 
+/// Returns none if the instruction isn't a jump. 
+/// 
+/// # Parameters 
+/// * `addr` - The address of the instruction. 
+/// * `stack` - The program stack containing the instruction. 
+/// 
+pub fn jump_target(addr: usize, stack: &ProgramStack) -> Option<usize> {
+    let instruction = BabyInstruction::from_number(stack[addr] as u16);
+    let pointer = stack[instruction.get_operand()];
+    match instruction {
+        BabyInstruction::Jump(_) => Some(pointer as usize & 0x1F),
+        BabyInstruction::RelativeJump(_) => Some((addr as u16).wrapping_add(pointer as u16) as usize & 0x1F),
+        _ => None
+    }
+}
+
+/// Gets the address a store instruction at an address will write to, read 
+/// from the pointer at its operand. 
+/// 
+/// Returns none if the instruction isn't a store. 
+/// 
+/// # Parameters 
+/// * `addr` - The address of the instruction. 
+/// * `stack` - The program stack containing the instruction. 
+/// 
+pub fn store_target(addr: usize, stack: &ProgramStack) -> Option<usize> {
+    match BabyInstruction::from_number(stack[addr] as u16) {
+        BabyInstruction::Store(p) => Some(stack[p as usize] as usize & 0x1F),
+        _ => None
+    }
+}
+
+/// Gets the addresses that can be executed directly after the instruction at an address. 
+/// 
+/// # Parameters 
+/// * `addr` - The address of the instruction. 
+/// * `stack` - The program stack containing the instruction. 
+/// 
+pub fn successors(addr: usize, stack: &ProgramStack) -> Vec<usize> {
+    match BabyInstruction::from_number(stack[addr] as u16) {
+        BabyInstruction::Stop => vec![],
+        BabyInstruction::SkipNextIfNegative => vec![(addr + 1) & 0x1F, (addr + 2) & 0x1F],
+        BabyInstruction::Jump(_) | BabyInstruction::RelativeJump(_) => 
+            jump_target(addr, stack).into_iter().collect(),
+        _ => vec![(addr + 1) & 0x1F]
+    }
+}
+
+/// Finds every address that can be executed when starting from address 0, 
+/// assuming the program doesn't modify its own instructions. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be analysed. 
+/// 
+pub fn reachable(stack: &ProgramStack) -> [bool; MEMORY_WORDS] {
+    let mut res = [false; MEMORY_WORDS];
+    let mut pending = vec![0];
+    while let Some(addr) = pending.pop() {
+        if res[addr] { continue; }
+        res[addr] = true;
+        pending.extend(successors(addr, stack));
+    }
+    res
+}
+
+/// Finds the probable code in a program, the reachable words that 
+/// are exactly encoded instructions. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be analysed. 
+/// 
+pub fn find_code(stack: &ProgramStack) -> [bool; MEMORY_WORDS] {
+    let reached = reachable(stack);
+    core::array::from_fn(|i| reached[i] && decode(stack[i]).is_some())
+}
+
+/// Finds the addresses referenced by code as operands. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be analysed. 
+/// * `code` - Which addresses contain code. 
+/// 
+pub fn find_operands(stack: &ProgramStack, code: &[bool; MEMORY_WORDS]) -> [bool; MEMORY_WORDS] {
+    let mut res = [false; MEMORY_WORDS];
+    (0..MEMORY_WORDS)
+        .filter(|i| code[*i])
+        .filter_map(|i| decode(stack[i]))
+        .filter(has_operand)
+        .for_each(|v| res[v.get_operand()] = true);
+    res
+}
+
+/// Finds the addresses code can jump to. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be analysed. 
+/// * `code` - Which addresses contain code. 
+/// 
+pub fn find_jump_targets(stack: &ProgramStack, code: &[bool; MEMORY_WORDS]) -> [bool; MEMORY_WORDS] {
+    let mut res = [false; MEMORY_WORDS];
+    (0..MEMORY_WORDS)
+        .filter(|i| code[*i])
+        .filter_map(|i| jump_target(i, stack))
+        .for_each(|v| res[v] = true);
+    res
+}
+
+/// Finds the addresses code stores the accumulator to, through the pointers 
+/// at each store's operand. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be analysed. 
+/// * `code` - Which addresses contain code. 
+/// 
+pub fn find_store_targets(stack: &ProgramStack, code: &[bool; MEMORY_WORDS]) -> [bool; MEMORY_WORDS] {
+    let mut res = [false; MEMORY_WORDS];
+    (0..MEMORY_WORDS)
+        .filter(|i| code[*i])
+        .filter_map(|i| store_target(i, stack))
+        .for_each(|v| res[v] = true);
+    res
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+use baby_emulator::core::MEMORY_WORDS;
+use crate::errors::{EXIT_FILE_ERROR, EXIT_INVALID_IMAGE};
+
+
+/// Defines common behaviour for errors thrown during disassembling. 
+pub trait DisasmError {
+    /// Gets a helper string describing an error. 
+    fn describe(&self) -> String;
+    /// Gets the process exit code to report the error with. 
+    fn exit_code(&self) -> i32;
+}
+
+/// Possible errors thrown during disassembling a binary image. 
+pub enum DisasmErrors {
+    /// Failed to read from a file. 
+    CouldntOpenFile(PathBuf),
+    /// Failed to write to a file. 
+    CouldNotWriteToFile(PathBuf),
+    /// The binary image is the wrong length. 
+    BinFileWrongLen(usize),
+    /// The image file was invalid. 
+    InvalidImage(String),
+}
+
+impl DisasmError for DisasmErrors {
+    fn describe(&self) -> String {
+        match self {
+            DisasmErrors::CouldntOpenFile(s) => 
+                format!("Couldn't open binary file: `{}`.", s.to_string_lossy()),
+            DisasmErrors::CouldNotWriteToFile(s) => 
+                format!("Couldn't write disassembled source to file: `{}`.", s.to_string_lossy()),
+            DisasmErrors::BinFileWrongLen(s) => 
+                format!("The baby memory is {} words long, specified file is `{}` words long. ", MEMORY_WORDS, s/4),
+            DisasmErrors::InvalidImage(s) => 
+                format!("Invalid image file. \n{}", s),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            DisasmErrors::CouldntOpenFile(_) => EXIT_FILE_ERROR,
+            DisasmErrors::CouldNotWriteToFile(_) => EXIT_FILE_ERROR,
+            DisasmErrors::BinFileWrongLen(_) => EXIT_INVALID_IMAGE,
+            DisasmErrors::InvalidImage(_) => EXIT_INVALID_IMAGE,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
+use crate::args::Disassemble;
+use crate::errors::Errors;
+use crate::interface::Interface;
+use crate::notation::{has_operand, write_instruction};
+use crate::run::ProgramStack;
+use crate::run::srcfile::read_words;
+use crate::image::Image;
+use analysis::{decode, find_code, find_operands, find_jump_targets};
+use errors::DisasmErrors;
+
+/// Possible errors thrown during disassembling. 
+pub mod errors;
+/// Contains helpers for analysing the control flow of a program. 
+pub mod analysis;
+#[cfg(test)]
+mod tests;
+
+
+/// The column source line comments are aligned to. 
+pub const COMMENT_COLUMN: usize = 24;
+
+/// Reads a raw binary image or an image file from an interface into a program stack. 
+/// 
+/// # Parameters 
+/// * `source` - The path to the binary image. 
+/// * `interface` - The interface to read from. 
+/// 
+/// # Returns 
+/// * [Ok(ProgramStack)] - The program stack read from the image. 
+/// * [Err(DisasmErrors)] - The image couldn't be read or is the wrong length. 
+/// 
+pub fn read_image(source: &Path, interface: &impl Interface) -> Result<ProgramStack, DisasmErrors> {
+    let raw = interface.read_fs_bytes(source)
+        .map_err(|_| DisasmErrors::CouldntOpenFile(source.to_path_buf()))?;
+
+    if Image::is_image(&raw) {
+        return Image::parse(&raw)
+            .map(|v| v.store)
+            .map_err(DisasmErrors::InvalidImage);
+    }
+
+    if raw.len() != MEMORY_WORDS * 4 {
+        return Err(DisasmErrors::BinFileWrongLen(raw.len()))
+    }
+
+    Ok(read_words(raw))
+}
+
+/// Synthesises tag names for the store lines code jumps to or uses as operands. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be named. 
+/// * `code` - Which addresses contain code. 
+/// 
+pub fn name_tags(stack: &ProgramStack, code: &[bool; MEMORY_WORDS]) -> [Option<String>; MEMORY_WORDS] {
+    let operands = find_operands(stack, code);
+    let targets = find_jump_targets(stack, code);
+    core::array::from_fn(|i| match (targets[i], operands[i]) {
+        (true, _) => Some(format!("label_{:02x}", i)),
+        (false, true) => Some(format!("data_{:02x}", i)),
+        _ => None
+    })
+}
+
+/// Writes a data word as an `abs` line, referencing a tag if the word is 
+/// a pointer used by a jump. 
+/// 
+/// # Parameters 
+/// * `word` - The word to be written. 
+/// * `tags` - The tag names of each address. 
+/// * `is_pointer` - Whether the word is a pointer used by a jump. 
+/// 
+fn write_data(word: i32, tags: &[Option<String>; MEMORY_WORDS], is_pointer: bool) -> String {
+    let tag = match (is_pointer, usize::try_from(word)) {
+        (true, Ok(v)) if v < MEMORY_WORDS => tags[v].as_ref(),
+        _ => None
+    };
+    match tag {
+        Some(t) => format!("abs ${}", t),
+        None => format!("abs 0d{}", word),
+    }
+}
+
+/// Disassembles a program stack into asm source lines, that assemble back 
+/// into the identical program stack. 
+/// 
+/// Reachable words that are exactly encoded instructions are written as 
+/// instructions, all other words as `abs` data, trailing zeroed words are 
+/// omitted as the assembler fills the rest of the store with zeros. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be disassembled. 
+/// * `og_notation` - Whether to write instructions in original notation. 
+/// 
+pub fn disassemble_stack(stack: &ProgramStack, og_notation: bool) -> Vec<String> {
+    let code = find_code(stack);
+    let tags = name_tags(stack, &code);
+    let mut pointers = [false; MEMORY_WORDS];
+    (0..MEMORY_WORDS)
+        .filter(|i| code[*i])
+        .filter_map(|i| decode(stack[i]))
+        .filter(|v| matches!(v, BabyInstruction::Jump(_)))
+        .for_each(|v| pointers[v.get_operand()] = true);
+
+    let last = (0..MEMORY_WORDS)
+        .rfind(|i| stack[*i] != 0 || tags[*i].is_some())
+        .map_or(0, |v| v + 1);
+
+    let mut res = vec![];
+    for i in 0..last {
+        if let Some(t) = &tags[i] { res.push(format!(":{}", t)); }
+        let line = match decode(stack[i]) {
+            Some(instr) if code[i] => {
+                let operand = if has_operand(&instr) {
+                    match &tags[instr.get_operand()] {
+                        Some(t) => format!("${}", t),
+                        None => format!("{:#04x}", instr.get_operand()),
+                    }
+                } else { String::new() };
+                write_instruction(&instr, &operand, og_notation)
+            },
+            _ => write_data(stack[i], &tags, pointers[i]),
+        };
+        res.push(format!("{:<width$}; {:#04x}", line, i, width = COMMENT_COLUMN));
+    }
+    res
+}
+
+/// Reads a binary image from an interface, disassembles it and writes the 
+/// asm source back to an interface. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to be used. 
+/// * `interface` - The interface to be used for writing/reading. 
+/// 
+/// # Returns 
+/// * [Ok(())] - Disassembling and writing happened sucessfully. 
+/// * [Err(Errors)] - An error was encountered during reading/writing. 
+/// 
+pub fn disassemble(conf: Disassemble, interface: &impl Interface) -> Result<(), Errors> {
+    let stack = read_image(&conf.input, interface)
+        .map_err(Errors::DisasmError)?;
+
+    let out = match &conf.output {
+        Some(v) => v.clone(),
+        None => PathBuf::from(conf.input.to_string_lossy().to_string() + ".asm")
+    };
+
+    let mut src = disassemble_stack(&stack, conf.og_notation).join("\n");
+    src.push('\n');
+
+    interface.write_fs_bytes(src.into_bytes(), &out)
+        .map_err(|_| Errors::DisasmError(DisasmErrors::CouldNotWriteToFile(out.clone())))?;
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use super::*;
+use baby_emulator::assembler::assemble;
+use baby_emulator::core::BabyModel;
+use crate::test_utils::TestInterface;
+
+
+fn reassemble(lines: Vec<String>, og_notation: bool) -> ProgramStack {
+    let asm = lines.join("\n");
+    match assemble(&asm, og_notation) {
+        Ok(v) => BabyInstruction::to_numbers(v),
+        Err(e) => panic!("Disassembled source failed to assemble: {}\n{}", e.describe(false), asm)
+    }
+}
+
+fn loop_program() -> ProgramStack {
+    BabyInstruction::to_numbers(vec![
+        BabyInstruction::Negate(7),
+        BabyInstruction::Subtract(8),
+        BabyInstruction::SkipNextIfNegative,
+        BabyInstruction::Jump(6),
+        BabyInstruction::Stop,
+        BabyInstruction::AbsoluteValue(0x1234_5678),
+        BabyInstruction::AbsoluteValue(1),
+        BabyInstruction::AbsoluteValue(-10),
+        BabyInstruction::AbsoluteValue(1),
+    ])
+}
+
+#[test]
+fn test_decode() {
+    assert_eq!(analysis::decode(0x4005), Some(BabyInstruction::Negate(5)));
+    assert_eq!(analysis::decode(0xE000), Some(BabyInstruction::Stop));
+    assert_eq!(analysis::decode(0xA005), None);
+    assert_eq!(analysis::decode(0x4025), None);
+    assert_eq!(analysis::decode(-10), None);
+}
+
+#[test]
+fn test_reachable() {
+    let reached = analysis::reachable(&loop_program());
+    assert_eq!(reached[0..9], [true, true, true, true, true, false, false, false, false]);
+    assert!(reached[9..].iter().all(|v| !v));
+}
+
+#[test]
+fn test_find_store_targets() {
+    let mut stack = loop_program();
+    stack[1] = BabyInstruction::Store(5).to_number();
+    stack[5] = 9;
+    let stored = analysis::find_store_targets(&stack, &analysis::find_code(&stack));
+    assert_eq!(stored.iter().filter(|v| **v).count(), 1);
+    assert!(stored[9]);
+}
+
+#[test]
+fn test_name_tags() {
+    let stack = loop_program();
+    let tags = name_tags(&stack, &analysis::find_code(&stack));
+    assert_eq!(tags[1], Some("label_01".to_string()));
+    assert_eq!(tags[6], Some("data_06".to_string()));
+    assert_eq!(tags[7], Some("data_07".to_string()));
+    assert_eq!(tags[5], None);
+}
+
+#[test]
+fn test_disassemble_stack() {
+    assert_eq!(disassemble_stack(&loop_program(), false), vec![
+        "ldn $data_07            ; 0x00",
+        ":label_01",
+        "sub $data_08            ; 0x01",
+        "cmp                     ; 0x02",
+        "jmp $data_06            ; 0x03",
+        "stp                     ; 0x04",
+        "abs 0d305419896         ; 0x05",
+        ":data_06",
+        "abs $label_01           ; 0x06",
+        ":data_07",
+        "abs 0d-10               ; 0x07",
+        ":data_08",
+        "abs 0d1                 ; 0x08",
+    ]);
+}
+
+#[test]
+fn test_disassemble_og_notation() {
+    let lines = disassemble_stack(&loop_program(), true);
+    assert_eq!(lines[0], "-$data_07, C            ; 0x00");
+    assert_eq!(lines[4], "$data_06, Cl            ; 0x03");
+    assert_eq!(reassemble(lines, true), loop_program());
+}
+
+#[test]
+fn test_disassemble_round_trip() {
+    assert_eq!(reassemble(disassemble_stack(&loop_program(), false), false), loop_program());
+    let example = BabyModel::new_example_program().main_store;
+    assert_eq!(reassemble(disassemble_stack(&example, false), false), example);
+    let empty = [0; MEMORY_WORDS];
+    assert_eq!(reassemble(disassemble_stack(&empty, false), false), empty);
+}
+
+#[test]
+fn test_disassemble_round_trip_arbitrary() {
+    let mut seed: u32 = 0x2545_F491;
+    let mut next = || { seed ^= seed << 13; seed ^= seed >> 17; seed ^= seed << 5; seed };
+    for _ in 0..200 {
+        let stack: ProgramStack = core::array::from_fn(|_| match next() % 4 {
+            0 => next() as i32,
+            1 => (next() % 32) as i32,
+            _ => (((next() % 8) << 13) | (next() % 32)) as i32,
+        });
+        assert_eq!(reassemble(disassemble_stack(&stack, false), false), stack);
+        assert_eq!(reassemble(disassemble_stack(&stack, true), true), stack);
+    }
+}
+
+#[test]
+fn test_read_image_wrong_len() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.bin_files.insert(PathBuf::from("foo"), vec![0; 12]);
+    match read_image(&PathBuf::from("foo"), &test_int) {
+        Err(DisasmErrors::BinFileWrongLen(12)) => (),
+        _ => panic!("Image of the wrong length was read. ")
+    }
+}
+
+#[test]
+fn test_disassemble() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    let stack = BabyInstruction::to_numbers(vec![BabyInstruction::Stop]);
+    test_int.bin_files.insert(PathBuf::from("foo.bin"), crate::assemble::format_data(Vec::from(stack), crate::args::Endianness::Big, false));
+    test_int.should_write_addr = PathBuf::from("foo.bin.asm");
+    test_int.should_write_data = "stp                     ; 0x00\n".to_string().into_bytes();
+    let conf = Disassemble { input: PathBuf::from("foo.bin"), output: None, og_notation: false };
+    assert!(disassemble(conf, &test_int).is_ok());
+}
@@ -1,21 +1,59 @@
 use crate::run::errors::{RunErrors, RunError};
 use crate::assemble::errors::{AsmErrors, AsmError};
+use crate::disassemble::errors::{DisasmErrors, DisasmError};
+use crate::link::errors::{LinkErrors, LinkError};
 
 
+/// Process exit code when a file couldn't be read or written. 
+pub const EXIT_FILE_ERROR: i32 = 3;
+/// Process exit code when an asm source file failed to assemble. 
+pub const EXIT_ASSEMBLER_ERROR: i32 = 4;
+/// Process exit code when a program image couldn't be loaded. 
+pub const EXIT_INVALID_IMAGE: i32 = 5;
+/// Process exit code when the emulator faulted executing an instruction. 
+pub const EXIT_EMULATION_FAULT: i32 = 6;
+/// Process exit code when the step budget was exceeded. 
+pub const EXIT_STEPS_EXCEEDED: i32 = 7;
+/// Process exit code when the program was proven to never stop. 
+pub const EXIT_NO_PROGRESS: i32 = 8;
+/// Process exit code when an asm source file isn't formatted (`fmt --check`). 
+pub const EXIT_NOT_FORMATTED: i32 = 9;
+/// Process exit code when object files failed to link. 
+pub const EXIT_LINK_ERROR: i32 = 10;
+/// Process exit code when a traced run was interrupted (Ctrl-C). 
+pub const EXIT_INTERRUPTED: i32 = 130;
+
 /// All the possible errors thrown during execution of the application. 
+#[allow(clippy::enum_variant_names)]
 pub enum Errors {
     /// An error encountered executing the emulation. 
     RuntimeError(RunErrors),
     /// An error encountered assembling an asm source file. 
-    AsmError(AsmErrors)
+    AsmError(AsmErrors),
+    /// An error encountered disassembling a binary image. 
+    DisasmError(DisasmErrors),
+    /// An error encountered linking object files. 
+    LinkError(LinkErrors),
 }
 
 impl Errors {
     /// Gets a helper string describing an error. 
     pub fn describe(&self) -> String {
         match self {
-            Errors::AsmError(v) => format!("{}", v.describe()),
-            Errors::RuntimeError(v) => format!("{}", v.describe()),
+            Errors::AsmError(v) => v.describe().to_string(),
+            Errors::RuntimeError(v) => v.describe().to_string(),
+            Errors::DisasmError(v) => v.describe().to_string(),
+            Errors::LinkError(v) => v.describe().to_string(),
+        }
+    }
+
+    /// Gets the process exit code to report an error with. 
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Errors::AsmError(v) => v.exit_code(),
+            Errors::RuntimeError(v) => v.exit_code(),
+            Errors::DisasmError(v) => v.exit_code(),
+            Errors::LinkError(v) => v.exit_code(),
         }
     }
 }
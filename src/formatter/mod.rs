@@ -0,0 +1,196 @@
+use baby_emulator::assembler::parser::{parse_line, strip_comments, LineType, Value, Instruction};
+use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
+use crate::args::Fmt;
+use crate::errors::Errors;
+use crate::interface::Interface;
+use crate::notation::{mnemonic, write_instruction};
+use crate::disassemble::COMMENT_COLUMN;
+use crate::assemble::diagnostics::{Diagnostic, diagnose_line, render_diagnostics};
+use crate::assemble::errors::{AsmErrors, SrcFileErrors};
+use crate::assemble::preprocess::{defined_names, mask_names, is_directive, is_invocation};
+
+#[cfg(test)]
+mod tests;
+
+
+/// The indentation of instruction & `abs` lines. 
+pub const INDENT: usize = 4;
+/// The width mnemonics are padded to so operands line up. 
+pub const MNEMONIC_WIDTH: usize = 4;
+
+/// Converts a parsed instruction to the instruction it assembles to, ignoring 
+/// the operand. 
+/// 
+/// # Parameters 
+/// * `instruction` - The parsed instruction. 
+/// 
+pub fn to_baby_instruction(instruction: &Instruction) -> BabyInstruction {
+    match instruction {
+        Instruction::Jump(_) => BabyInstruction::Jump(0),
+        Instruction::RelativeJump(_) => BabyInstruction::RelativeJump(0),
+        Instruction::Negate(_) => BabyInstruction::Negate(0),
+        Instruction::Store(_) => BabyInstruction::Store(0),
+        Instruction::Subtract(_) => BabyInstruction::Subtract(0),
+        Instruction::Test => BabyInstruction::SkipNextIfNegative,
+        Instruction::Stop => BabyInstruction::Stop,
+    }
+}
+
+/// Formats an instruction operand, store addresses in hex, anything else in decimal. 
+/// 
+/// # Parameters 
+/// * `value` - The operand value. 
+/// 
+pub fn format_operand(value: &Value) -> String {
+    match value {
+        Value::Tag(t) => format!("${}", t),
+        Value::Value(v) if (0..MEMORY_WORDS as i32).contains(v) => format!("{:#04x}", v),
+        Value::Value(v) => format!("0d{}", v),
+    }
+}
+
+/// Formats an `abs` value in decimal. 
+/// 
+/// # Parameters 
+/// * `value` - The absolute value. 
+/// 
+pub fn format_absolute(value: &Value) -> String {
+    match value {
+        Value::Tag(t) => format!("${}", t),
+        Value::Value(v) => format!("0d{}", v),
+    }
+}
+
+/// Formats an instruction or `abs` line without indentation or comment. 
+/// 
+/// # Parameters 
+/// * `line` - The parsed line. 
+/// * `og_notation` - Whether to write in original notation. 
+/// 
+pub fn format_code(line: &LineType, og_notation: bool) -> String {
+    match line {
+        LineType::Tag(t) => format!(":{}", t),
+        LineType::Absolute(v) if og_notation => format!("abs {}", format_absolute(v)),
+        LineType::Absolute(v) => format!("{:<width$}{}", "abs", format_absolute(v), width = MNEMONIC_WIDTH),
+        LineType::Instruction(i) => {
+            let instruction = to_baby_instruction(i);
+            match (og_notation, i) {
+                (true, _) => write_instruction(&instruction, &format_operand(&i.get_operand()), true),
+                (false, Instruction::Test | Instruction::Stop) => mnemonic(&instruction).to_lowercase(),
+                (false, _) => format!(
+                    "{:<width$}{}", mnemonic(&instruction).to_lowercase(),
+                    format_operand(&i.get_operand()), width = MNEMONIC_WIDTH
+                ),
+            }
+        },
+    }
+}
+
+/// Gets the comment on a line, trimmed, if there is one. 
+/// 
+/// # Parameters 
+/// * `line` - The source line. 
+/// 
+pub fn comment_of(line: &str) -> Option<&str> {
+    line.split_once(";").map(|(_, c)| c.trim())
+}
+
+/// Appends a comment to a formatted line, aligned to the comment column. 
+/// 
+/// # Parameters 
+/// * `code` - The formatted line. 
+/// * `comment` - The comment, if any. 
+/// 
+pub fn with_comment(code: String, comment: Option<&str>) -> String {
+    let comment = match comment {
+        Some("") => ";".to_string(),
+        Some(c) => format!("; {}", c),
+        None => return code,
+    };
+    if code.is_empty() { return comment; }
+    let width = COMMENT_COLUMN.max(code.len() + 1);
+    format!("{:<width$}{}", code, comment, width = width)
+}
+
+/// Rewrites an asm source in the canonical layout. 
+/// 
+/// Tags, directives & comment only lines start at the first column, instructions 
+/// & `abs` lines are indented with operands & comments aligned, operands are 
+/// written in hex & `abs` values in decimal, runs of blank lines are collapsed 
+/// and tags are placed directly above the line they tag. Lines using constants 
+/// or macro parameters and macro invocations are only indented. 
+/// 
+/// # Parameters 
+/// * `asm` - The asm source. 
+/// * `og_notation` - Whether the source uses original notation. 
+/// 
+/// # Returns 
+/// * [Ok(String)] - The formatted source. 
+/// * [Err(Vec<Diagnostic>)] - Lines of the source couldn't be parsed. 
+/// 
+pub fn format_source(asm: &str, og_notation: bool) -> Result<String, Vec<Diagnostic>> {
+    let names = defined_names(asm);
+    let mut res: Vec<String> = vec![];
+    let mut errors = vec![];
+    let mut blank = false;
+    let mut after_tag = false;
+    for (i, raw) in asm.lines().enumerate() {
+        let code = strip_comments(raw);
+        let comment = comment_of(raw);
+        let line = if code.trim().is_empty() {
+            match comment {
+                Some(_) => with_comment(String::new(), comment),
+                None => { blank = true; continue; },
+            }
+        } else if is_directive(raw) {
+            with_comment(code.trim().to_owned(), comment)
+        } else {
+            match parse_line(&raw.to_owned(), og_notation) {
+                Ok(l @ LineType::Tag(_)) => with_comment(format_code(&l, og_notation), comment),
+                Ok(l) => with_comment(format!("{}{}", " ".repeat(INDENT), format_code(&l, og_notation)), comment),
+                Err(_) if is_invocation(raw, &names) || parse_line(&mask_names(raw, &names), og_notation).is_ok() =>
+                    with_comment(format!("{}{}", " ".repeat(INDENT), code.trim()), comment),
+                Err(e) => { errors.push(diagnose_line(i + 1, raw, &e, og_notation)); continue; },
+            }
+        };
+        if blank && !res.is_empty() && !after_tag { res.push(String::new()); }
+        after_tag = code.trim_start().starts_with(":");
+        blank = false;
+        res.push(line);
+    }
+    if !errors.is_empty() { return Err(errors); }
+    Ok(res.into_iter().map(|v| v + "\n").collect())
+}
+
+/// Reads an asm source file from an interface and rewrites it in the canonical 
+/// layout, or with `check` only checks that it's already formatted. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to be used. 
+/// * `interface` - The interface to be used for reading & writing. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The file was formatted, or is already formatted. 
+/// * [Err(Errors)] - The file couldn't be read, parsed or written, or isn't formatted when checking. 
+/// 
+pub fn fmt(conf: Fmt, interface: &impl Interface) -> Result<(), Errors> {
+    let asm = interface.read_fs_string(&conf.input)
+        .map_err(|_| Errors::AsmError(AsmErrors::SrcFileError(SrcFileErrors::CouldntOpenFile(conf.input.clone()))))?;
+
+    let formatted = format_source(&asm, conf.og_notation)
+        .map_err(|e| Errors::AsmError(AsmErrors::AssembleError(render_diagnostics(&conf.input, &asm, &e))))?;
+
+    let name = conf.input.to_string_lossy().to_string();
+    if formatted == asm {
+        interface.log_msg(format!("`{}` is already formatted. ", name));
+        return Ok(());
+    }
+    if conf.check {
+        return Err(Errors::AsmError(AsmErrors::NotFormatted(conf.input.clone())));
+    }
+
+    interface.write_fs_bytes(formatted.into_bytes(), &conf.input)
+        .map_err(|_| Errors::AsmError(AsmErrors::SrcFileError(SrcFileErrors::CouldNotWriteToFile(conf.input.clone()))))?;
+    interface.log_msg(format!("Formatted `{}`. ", name));
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+use super::*;
+use baby_emulator::assembler::assemble;
+use crate::test_utils::TestInterface;
+
+
+const UNFORMATTED: &str = "
+
+  ; Counts down from 10
+LDN   $start_value ;load
+
+:loop_start_value
+
+sub $subtract_val;Subtract 1
+   cmp
+jmp 0d6
+
+
+stp
+:loop_start ;
+abs $loop_start_value
+:subtract_val
+abs 0x1
+:start_value
+ abs 0d-10
+";
+
+const FORMATTED: &str = "; Counts down from 10
+    ldn $start_value    ; load
+
+:loop_start_value
+    sub $subtract_val   ; Subtract 1
+    cmp
+    jmp 0x06
+
+    stp
+:loop_start             ;
+    abs $loop_start_value
+:subtract_val
+    abs 0d1
+:start_value
+    abs 0d-10
+";
+
+#[test]
+fn test_format_operand() {
+    assert_eq!(format_operand(&Value::Value(31)), "0x1f");
+    assert_eq!(format_operand(&Value::Value(32)), "0d32");
+    assert_eq!(format_operand(&Value::Value(-1)), "0d-1");
+    assert_eq!(format_operand(&Value::Tag("foo".to_string())), "$foo");
+    assert_eq!(format_absolute(&Value::Value(16)), "0d16");
+}
+
+#[test]
+fn test_with_comment() {
+    assert_eq!(with_comment("    stp".to_string(), None), "    stp");
+    assert_eq!(with_comment("    stp".to_string(), Some("end")), format!("{:<24}; end", "    stp"));
+    assert_eq!(with_comment(String::new(), Some("end")), "; end");
+    let long = "a".repeat(30);
+    assert_eq!(with_comment(long.clone(), Some("")), format!("{} ;", long));
+}
+
+#[test]
+fn test_format_source() {
+    let res = format_source(UNFORMATTED, false).ok().unwrap();
+    assert_eq!(res, FORMATTED);
+    assert_eq!(format_source(&res, false).ok().unwrap(), res);
+    assert_eq!(
+        assemble(&UNFORMATTED.to_owned(), false).ok().unwrap(),
+        assemble(&res, false).ok().unwrap()
+    );
+    assert_eq!(format_source("", false).ok().unwrap(), "");
+}
+
+#[test]
+fn test_format_source_og_notation() {
+    let res = format_source("-0d7, C\nSUB 0x8\n  Test\nAdd 0d1, Cl\nStop", true).ok().unwrap();
+    assert_eq!(res, "    -0x07, C\n    SUB 0x08\n    Test\n    Add 0x01, Cl\n    Stop\n");
+}
+
+#[test]
+fn test_format_source_errors() {
+    let res = format_source("stp\nfoo\nabs 12", false).err().unwrap();
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].line, 2);
+    assert_eq!(res[1].line, 3);
+}
+
+#[test]
+fn test_fmt() {
+    let conf = || Fmt { input: PathBuf::from("foo.asm"), og_notation: false, check: false };
+    let mut test_int = TestInterface::new_logger_test("Formatted `foo.asm`. ", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), UNFORMATTED.to_owned());
+    test_int.should_write_addr = PathBuf::from("foo.asm");
+    test_int.should_write_data = FORMATTED.to_owned().into_bytes();
+    assert!(fmt(conf(), &test_int).is_ok());
+
+    let mut test_int = TestInterface::new_logger_test("`foo.asm` is already formatted. ", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), FORMATTED.to_owned());
+    assert!(fmt(Fmt { check: true, ..conf() }, &test_int).is_ok());
+
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), UNFORMATTED.to_owned());
+    match fmt(Fmt { check: true, ..conf() }, &test_int) {
+        Err(e) => assert_eq!(e.exit_code(), crate::errors::EXIT_NOT_FORMATTED),
+        Ok(_) => panic!()
+    }
+}
+
+#[test]
+fn test_format_source_directives() {
+    let asm = "  %include \"lib.asm\"\n%define X 0d3\n%macro neg a\n ldn   a ; load\n%endmacro\n  neg X\nsub X\n".to_string();
+    assert_eq!(format_source(&asm, false).ok().unwrap(), format!(
+        "%include \"lib.asm\"\n%define X 0d3\n%macro neg a\n    ldn   a             ; load\n%endmacro\n    neg X\n    sub X\n"
+    ));
+}
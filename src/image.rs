@@ -0,0 +1,179 @@
+use baby_emulator::core::{BabyModel, MEMORY_WORDS};
+use crate::run::ProgramStack;
+
+
+/// The first bytes of every image file, identifying the format. 
+pub const IMAGE_MAGIC: &[u8; 4] = b"SSEM";
+
+/// The version of the image format written. 
+pub const IMAGE_VERSION: u16 = 1;
+
+/// The header flag set if the program was assembled with original notation. 
+pub const FLAG_OG_NOTATION: u16 = 1;
+
+/// The length of the fixed part of the header, before the program name. 
+const FIXED_HEADER_LEN: usize = 16;
+
+/// The length of the CRC at the end of the image. 
+const CRC_LEN: usize = 4;
+
+/// The longest name in bytes that fits in the u16 length field of the header. 
+pub const MAX_NAME_LEN: usize = u16::MAX as usize;
+
+/// Calculates the CRC-32 (IEEE) of some bytes. 
+/// 
+/// # Parameters 
+/// * `data` - The bytes to be checked. 
+/// 
+pub fn crc32(data: &[u8]) -> u32 {
+    let crc = data.iter().fold(0xFFFF_FFFF_u32, |crc, b| {
+        (0..8).fold(crc ^ *b as u32, |crc, _| 
+            if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 }
+        )
+    });
+    !crc
+}
+
+/// A program image carrying the store along with the initial machine state 
+/// and metadata about the program. 
+/// 
+/// Serialised big endian as the magic `SSEM`, the version (u16), flags (u16), 
+/// the initial instruction address (u16), the initial accumulator (i32), the 
+/// length of the name (u16), the name (UTF-8), the store (32 i32s) and a 
+/// CRC-32 of everything before it (u32). 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Image {
+    /// The name of the program. 
+    pub name: String,
+    /// Whether the program was assembled with original notation. 
+    pub og_notation: bool,
+    /// The address of the first instruction to execute. 
+    pub instruction_address: u16,
+    /// The initial value of the accumulator. 
+    pub accumulator: i32,
+    /// The store. 
+    pub store: ProgramStack,
+}
+
+/// Reads a big endian u16 from some bytes. 
+fn read_u16(raw: &[u8], at: usize) -> u16 {
+    u16::from_be_bytes([raw[at], raw[at + 1]])
+}
+
+/// Reads a big endian i32 from some bytes. 
+fn read_i32(raw: &[u8], at: usize) -> i32 {
+    i32::from_be_bytes([raw[at], raw[at + 1], raw[at + 2], raw[at + 3]])
+}
+
+impl Image {
+    /// Creates an image of a store, starting at address 0 with an empty accumulator. 
+    /// 
+    /// # Parameters 
+    /// * `name` - The name of the program. 
+    /// * `store` - The store. 
+    /// 
+    pub fn new(name: &str, store: ProgramStack) -> Image {
+        Image { 
+            name: name.to_owned(), 
+            og_notation: false, 
+            instruction_address: 0, 
+            accumulator: 0, 
+            store 
+        }
+    }
+
+    /// Determines if some bytes start with the image magic, rather than being a raw store. 
+    /// 
+    /// Exactly a store's worth of bytes is always a raw store, as an image 
+    /// carries a header and CRC as well, so a store whose first word happens 
+    /// to spell the magic isn't mistaken for an image. 
+    /// 
+    /// # Parameters 
+    /// * `raw` - The bytes to be checked. 
+    /// 
+    pub fn is_image(raw: &[u8]) -> bool {
+        raw.starts_with(IMAGE_MAGIC) && raw.len() != MEMORY_WORDS * 4
+    }
+
+    /// Gets the name as written to the header, truncated on a character 
+    /// boundary to [MAX_NAME_LEN] bytes. 
+    pub fn header_name(&self) -> &str {
+        let len = (0..=self.name.len().min(MAX_NAME_LEN)).rev()
+            .find(|v| self.name.is_char_boundary(*v))
+            .unwrap_or(0);
+        &self.name[..len]
+    }
+
+    /// Serialises the image to bytes to be saved to a file, names too long 
+    /// for the header are truncated. 
+    pub fn serialise(&self) -> Vec<u8> {
+        let flags = if self.og_notation { FLAG_OG_NOTATION } else { 0 };
+        let mut res = IMAGE_MAGIC.to_vec();
+        res.extend(IMAGE_VERSION.to_be_bytes());
+        res.extend(flags.to_be_bytes());
+        res.extend(self.instruction_address.to_be_bytes());
+        res.extend(self.accumulator.to_be_bytes());
+        let name = self.header_name();
+        res.extend((name.len() as u16).to_be_bytes());
+        res.extend(name.as_bytes());
+        self.store.iter().for_each(|v| res.extend(v.to_be_bytes()));
+        res.extend(crc32(&res).to_be_bytes());
+        res
+    }
+
+    /// Parses an image from bytes. 
+    /// 
+    /// # Parameters 
+    /// * `raw` - The bytes to be parsed. 
+    /// 
+    /// # Returns 
+    /// * [Ok(Image)] - The parsed image. 
+    /// * [Err(String)] - A formatted error message if the image is invalid. 
+    /// 
+    pub fn parse(raw: &[u8]) -> Result<Image, String> {
+        if !raw.starts_with(IMAGE_MAGIC) {
+            return Err("Missing magic `SSEM`. ".to_string());
+        }
+        if raw.len() < FIXED_HEADER_LEN {
+            return Err("The header is truncated. ".to_string());
+        }
+        let version = read_u16(raw, 4);
+        if version != IMAGE_VERSION {
+            return Err(format!("Unsupported image version `{}`, expected `{}`. ", version, IMAGE_VERSION));
+        }
+        let name_len = read_u16(raw, 14) as usize;
+        let store_at = FIXED_HEADER_LEN + name_len;
+        let crc_at = store_at + MEMORY_WORDS * 4;
+        if raw.len() != crc_at + CRC_LEN {
+            return Err(format!("Expected {} bytes, found {}. ", crc_at + CRC_LEN, raw.len()));
+        }
+        let crc = read_i32(raw, crc_at) as u32;
+        if crc != crc32(&raw[..crc_at]) {
+            return Err("The checksum doesn't match, the image is corrupt. ".to_string());
+        }
+
+        let instruction_address = read_u16(raw, 8);
+        if instruction_address as usize >= MEMORY_WORDS {
+            return Err(format!("Invalid instruction address {:#06x}. ", instruction_address));
+        }
+        let name = String::from_utf8(raw[FIXED_HEADER_LEN..store_at].to_vec())
+            .map_err(|_| "The program name isn't valid UTF-8. ".to_string())?;
+        Ok(Image {
+            name,
+            og_notation: read_u16(raw, 6) & FLAG_OG_NOTATION != 0,
+            instruction_address,
+            accumulator: read_i32(raw, 10),
+            store: core::array::from_fn(|i| read_i32(raw, store_at + i * 4)),
+        })
+    }
+
+    /// Creates a model from the image, ready to execute the first instruction. 
+    pub fn model(&self) -> BabyModel {
+        BabyModel {
+            main_store: self.store,
+            accumulator: self.accumulator,
+            instruction_address: self.instruction_address,
+            instruction: self.store[self.instruction_address as usize] as u16,
+        }
+    }
+}
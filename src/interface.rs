@@ -2,7 +2,10 @@ use std::io;
 use std::fs;
 use std::io::Write;
 use std::io::stdout;
-use std::path::PathBuf;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
 use colored::Colorize;
 
 
@@ -19,13 +22,25 @@ pub trait Interface {
     /// Gets a line of input from a user. 
     fn get_line(&self) -> String;
     /// Reads a file to a string. 
-    fn read_fs_string(&self, path: &PathBuf) -> Result<String, ()>;
+    fn read_fs_string(&self, path: &Path) -> Result<String, ()>;
     /// Reads a file to bytes. 
-    fn read_fs_bytes(&self, path: &PathBuf) -> Result<Vec<u8>, ()>;
+    fn read_fs_bytes(&self, path: &Path) -> Result<Vec<u8>, ()>;
     /// Writes bytes to a file. 
-    fn write_fs_bytes(&self, data: Vec<u8>, out: &PathBuf) -> Result<(), ()>;
+    fn write_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()>;
+    /// Appends bytes to a file, creating it if it doesn't exist. 
+    fn append_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()>;
+    /// Waits for a duration. 
+    fn sleep(&self, duration: Duration);
+    /// Catches interrupts (Ctrl-C) from now on rather than letting them end 
+    /// the program, so they can be checked for with [Interface::interrupted]. 
+    fn watch_interrupts(&self);
+    /// Checks if an interrupt has been caught since the last check. 
+    fn interrupted(&self) -> bool;
 }
 
+/// Set when an interrupt is caught after [Interface::watch_interrupts]. 
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
 /// An interface for interacting with the cli. 
 pub struct CliInterface();
 
@@ -49,17 +64,31 @@ impl Interface for CliInterface {
         let _ = io::stdin().read_line(&mut line);
         line
     }
-    fn read_fs_string(&self, path: &PathBuf) -> Result<String, ()> {
+    fn read_fs_string(&self, path: &Path) -> Result<String, ()> {
         fs::read_to_string(path)
             .map_err(|_| ())
     }
-    fn read_fs_bytes(&self, path: &PathBuf) -> Result<Vec<u8>, ()> {
+    fn read_fs_bytes(&self, path: &Path) -> Result<Vec<u8>, ()> {
         fs::read(path)
             .map_err(|_| ())
     }
-    fn write_fs_bytes(&self, data: Vec<u8>, out: &PathBuf) -> Result<(), ()> {
-        fs::write(&out, &data)
+    fn write_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
+        fs::write(out, &data)
+            .map_err(|_| ())
+    }
+    fn append_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
+        fs::OpenOptions::new().create(true).append(true).open(out)
+            .and_then(|mut f| f.write_all(&data))
             .map_err(|_| ())
     }
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+    fn watch_interrupts(&self) {
+        let _ = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst));
+    }
+    fn interrupted(&self) -> bool {
+        INTERRUPTED.swap(false, Ordering::SeqCst)
+    }
 }
 
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use crate::errors::{EXIT_FILE_ERROR, EXIT_INVALID_IMAGE, EXIT_LINK_ERROR};
+
+
+/// Defines common behaviour for errors thrown during linking. 
+pub trait LinkError {
+    /// Gets a helper string describing an error. 
+    fn describe(&self) -> String;
+    /// Gets the process exit code to report the error with. 
+    fn exit_code(&self) -> i32;
+}
+
+/// Possible errors thrown during linking object files. 
+pub enum LinkErrors {
+    /// Failed to read from a file. 
+    CouldntOpenFile(PathBuf),
+    /// Failed to write to a file. 
+    CouldNotWriteToFile(PathBuf),
+    /// An object file was invalid. 
+    InvalidObject(PathBuf, String),
+    /// Error resolving or placing the objects, with every error found rendered. 
+    LinkError(String),
+}
+
+impl LinkError for LinkErrors {
+    fn describe(&self) -> String {
+        match self {
+            LinkErrors::CouldntOpenFile(s) => 
+                format!("Couldn't open object file: `{}`.", s.to_string_lossy()),
+            LinkErrors::CouldNotWriteToFile(s) => 
+                format!("Couldn't write linked data to file: `{}`.", s.to_string_lossy()),
+            LinkErrors::InvalidObject(p, s) => 
+                format!("Invalid object file `{}`. \n{}", p.to_string_lossy(), s),
+            LinkErrors::LinkError(s) => format!("Problem linking files. \n{}", s),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            LinkErrors::CouldntOpenFile(_) => EXIT_FILE_ERROR,
+            LinkErrors::CouldNotWriteToFile(_) => EXIT_FILE_ERROR,
+            LinkErrors::InvalidObject(_, _) => EXIT_INVALID_IMAGE,
+            LinkErrors::LinkError(_) => EXIT_LINK_ERROR,
+        }
+    }
+}
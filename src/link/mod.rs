@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+use baby_emulator::core::MEMORY_WORDS;
+use crate::args::{Link, OutputFormat};
+use crate::errors::Errors;
+use crate::interface::Interface;
+use crate::run::ProgramStack;
+use crate::object::Object;
+use crate::symbols::{Symbols, symbol_path};
+use crate::snp::serialise_snp;
+use crate::image::Image;
+use crate::assemble::{format_data, format_extension};
+use errors::LinkErrors;
+
+/// Possible errors thrown during linking. 
+pub mod errors;
+#[cfg(test)]
+mod tests;
+
+
+/// Where an object is placed in the store. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Placement {
+    /// The path of the object file. 
+    pub path: PathBuf,
+    /// The store address of the object's first word. 
+    pub base: usize,
+    /// The number of words in the object. 
+    pub len: usize,
+}
+
+/// Places objects one after another in the store, starting at store line 0. 
+/// 
+/// # Parameters 
+/// * `objects` - The object file paths & objects, in the order to be placed. 
+/// 
+pub fn place(objects: &[(PathBuf, Object)]) -> Vec<Placement> {
+    let mut base = 0;
+    objects.iter().map(|(path, object)| {
+        let res = Placement { path: path.clone(), base, len: object.words.len() };
+        base += object.words.len();
+        res
+    }).collect()
+}
+
+/// Renders link errors, followed by the number of errors. 
+/// 
+/// # Parameters 
+/// * `errors` - The errors, each possibly followed by note lines. 
+/// 
+fn render_errors(errors: &[String]) -> String {
+    let mut res: Vec<String> = errors.iter().map(|v| format!("error: {}\n", v)).collect();
+    let count = errors.len();
+    res.push(format!("{} error{} found. ", count, if count == 1 { "" } else { "s" }));
+    res.join("\n")
+}
+
+/// Links objects into a program stack, placing them one after another from 
+/// store line 0 and resolving every tag each object imports to the address 
+/// exported by another. 
+/// 
+/// # Parameters 
+/// * `objects` - The object file paths & objects, in the order to be placed. 
+/// 
+/// # Returns 
+/// * [Ok((ProgramStack, Symbols))] - The linked program stack and the exported tags. 
+/// * [Err(String)] - Every error found, rendered. 
+/// 
+pub fn link_objects(objects: &[(PathBuf, Object)]) -> Result<(ProgramStack, Symbols), String> {
+    let placements = place(objects);
+    let name = |i: usize| placements[i].path.to_string_lossy().to_string();
+    let mut errors = vec![];
+
+    let mut exported: Vec<(String, usize, usize)> = vec![];
+    for (i, (_, object)) in objects.iter().enumerate() {
+        for (tag, addr) in &object.exports {
+            match exported.iter().find(|(t, _, _)| t == tag) {
+                Some((_, _, j)) => errors.push(format!("the tag `{}` is exported by both `{}` and `{}`", tag, name(*j), name(i))),
+                None => exported.push((tag.clone(), placements[i].base + addr, i)),
+            }
+        }
+    }
+    for (i, (_, object)) in objects.iter().enumerate() {
+        object.imports.iter()
+            .filter(|v| !exported.iter().any(|(t, _, _)| t == *v))
+            .for_each(|v| errors.push(format!("the tag `{}` imported by `{}` isn't exported by any object", v, name(i))));
+    }
+
+    let len = placements.last().map_or(0, |v| v.base + v.len);
+    if len > MEMORY_WORDS {
+        let mut message = format!("the linked program is {} words long, the store is only {} words", len, MEMORY_WORDS);
+        for p in &placements {
+            message += &format!("\n  = note: `{}` is {} words, placed at {:#04x}", p.path.to_string_lossy(), p.len, p.base);
+        }
+        errors.push(message);
+    }
+    if !errors.is_empty() { return Err(render_errors(&errors)); }
+
+    let mut res = [0; MEMORY_WORDS];
+    for ((_, object), placement) in objects.iter().zip(&placements) {
+        let base = placement.base;
+        res[base..base + object.words.len()].copy_from_slice(&object.words);
+        for r in &object.references {
+            let word = res[base + r.addr];
+            let addr = match &r.symbol {
+                Some(s) => exported.iter().find(|(t, _, _)| t == s).map_or(0, |(_, a, _)| *a as i32),
+                None => r.field.get(word) + base as i32,
+            };
+            res[base + r.addr] = r.field.set(word, addr);
+        }
+    }
+    let symbols = Symbols::from_entries(exported.into_iter().map(|(t, a, _)| (t, a)).collect());
+    Ok((res, symbols))
+}
+
+/// Reads and parses object files from an interface. 
+/// 
+/// # Parameters 
+/// * `inputs` - The paths of the object files. 
+/// * `interface` - The interface to read from. 
+/// 
+/// # Returns 
+/// * [Ok(Vec<(PathBuf, Object)>)] - The object file paths & objects. 
+/// * [Err(LinkErrors)] - A file couldn't be read or isn't a valid object. 
+/// 
+pub fn read_objects(inputs: &[PathBuf], interface: &impl Interface) -> Result<Vec<(PathBuf, Object)>, LinkErrors> {
+    inputs.iter().map(|path| {
+        let src = interface.read_fs_string(path)
+            .map_err(|_| LinkErrors::CouldntOpenFile(path.clone()))?;
+        let object = Object::parse(&src)
+            .map_err(|e| LinkErrors::InvalidObject(path.clone(), e))?;
+        Ok((path.clone(), object))
+    }).collect()
+}
+
+/// Gets the path the output file is written to, the configured output or 
+/// the first input + `.bin`/`.snp`/`.img` depending on the format. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to be used. 
+/// 
+pub fn output_path(conf: &Link) -> PathBuf {
+    match &conf.output {
+        Some(v) => v.clone(),
+        None => PathBuf::from(format!("{}.{}", conf.inputs[0].to_string_lossy(), format_extension(conf.format)))
+    }
+}
+
+/// Reads object files from an interface, links them and writes the program 
+/// in the configured format to an interface, along with a symbol file of the 
+/// exported tags if there are any. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to be used. 
+/// * `interface` - The interface to be used for reading & writing. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The objects were linked and written. 
+/// * [Err(Errors)] - An object couldn't be read or linked, or the output couldn't be written. 
+/// 
+pub fn link(conf: Link, interface: &impl Interface) -> Result<(), Errors> {
+    let objects = read_objects(&conf.inputs, interface)
+        .map_err(Errors::LinkError)?;
+
+    let (stack, symbols) = link_objects(&objects)
+        .map_err(|e| Errors::LinkError(LinkErrors::LinkError(e)))?;
+
+    let out = output_path(&conf);
+    let data = match conf.format {
+        OutputFormat::Bin => format_data(Vec::from(stack), conf.endian, false),
+        OutputFormat::Snp => serialise_snp(&stack).into_bytes(),
+        OutputFormat::Image => {
+            let name = out.file_stem().map(|v| v.to_string_lossy().to_string()).unwrap_or_default();
+            Image::new(&name, stack).serialise()
+        },
+    };
+    interface.write_fs_bytes(data, &out)
+        .map_err(|_| Errors::LinkError(LinkErrors::CouldNotWriteToFile(out.clone())))?;
+
+    if !symbols.is_empty() {
+        let sym = symbol_path(&out);
+        interface.write_fs_bytes(symbols.serialise().into_bytes(), &sym)
+            .map_err(|_| Errors::LinkError(LinkErrors::CouldNotWriteToFile(sym.clone())))?;
+    }
+
+    let used: usize = place(&objects).iter().map(|v| v.len).sum();
+    interface.log_msg(format!("Linked {} object{} into {} of {} store lines. ",
+        objects.len(), if objects.len() == 1 { "" } else { "s" }, used, MEMORY_WORDS));
+    Ok(())
+}
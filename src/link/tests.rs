@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+use super::*;
+use baby_emulator::core::instructions::BabyInstruction;
+use crate::args::Endianness;
+use crate::object::{Reference, Field};
+use crate::assemble::preprocess::preprocess;
+use crate::assemble::object::assemble_object;
+use crate::test_utils::TestInterface;
+
+
+const MAIN_ASM: &str = "%import double
+%export start
+:start
+    ldn $x
+    jmp $ptr
+    stp
+:ptr
+    abs $double
+:x
+    abs 0d5
+";
+
+const LIB_ASM: &str = "%export double
+%export one
+:one
+    abs 0d1
+:double
+    sub $one
+    stp
+";
+
+fn object_of(asm: &str) -> Result<Object, String> {
+    let test_int = TestInterface::new_logger_test("", "", "");
+    let pre = preprocess(&PathBuf::from("foo.asm"), asm, &test_int)?;
+    assemble_object(&pre, false)
+}
+
+fn objects() -> Vec<(PathBuf, Object)> {
+    vec![
+        (PathBuf::from("main.obj"), object_of(MAIN_ASM).ok().unwrap()),
+        (PathBuf::from("lib.obj"), object_of(LIB_ASM).ok().unwrap()),
+    ]
+}
+
+#[test]
+fn test_assemble_object() {
+    let object = object_of(MAIN_ASM).ok().unwrap();
+    assert_eq!(object.words, vec![
+        BabyInstruction::Negate(4).to_number(),
+        BabyInstruction::Jump(3).to_number(),
+        BabyInstruction::Stop.to_number(),
+        0,
+        5,
+    ]);
+    assert_eq!(object.exports, vec![("start".to_string(), 0)]);
+    assert_eq!(object.imports, vec![format!("double")]);
+    assert_eq!(object.references, vec![
+        Reference { addr: 0, field: Field::Operand, symbol: None },
+        Reference { addr: 1, field: Field::Operand, symbol: None },
+        Reference { addr: 3, field: Field::Word, symbol: Some("double".to_string()) },
+    ]);
+}
+
+#[test]
+fn test_assemble_object_errors() {
+    let res = object_of("%import x\n%export strat\n%export x\n:start\n    ldn $x\n    sub $y\n:x\n    abs 0d1").err().unwrap();
+    assert!(res.contains("error: the tag `y` is not declared\n --> foo.asm:6:9"));
+    assert!(res.contains("error: the tag `x` is imported but also declared in this file\n --> foo.asm:1:9"));
+    assert!(res.contains("error: the tag `strat` is exported but not declared\n --> foo.asm:2:9"));
+    assert!(res.contains("= help: did you mean `start`?"));
+    assert!(res.contains("error: the tag `x` is imported, it can't be exported\n --> foo.asm:3:9"));
+    assert!(res.ends_with("4 errors found. "));
+
+    assert_eq!(object_of("%export end\n    stp\n:end").err(), Some("The tag `end` doesn't label a store line. ".to_string()));
+}
+
+#[test]
+fn test_object_serialise() {
+    let object = object_of(MAIN_ASM).ok().unwrap();
+    let src = object.serialise();
+    assert_eq!(src, format!(
+        "ssemu-object 1\nword 16388\nword 3\nword 57344\nword 0\nword 5\nexport 0x00 start\nimport double\n\
+        ref 0x00 operand\nref 0x01 operand\nref 0x03 word double\n"
+    ));
+    assert_eq!(Object::parse(&src), Ok(object));
+
+    assert_eq!(Object::parse("word 1"), Err("Missing magic `ssemu-object`. ".to_string()));
+    assert_eq!(Object::parse("ssemu-object 2"), Err("Unsupported object `ssemu-object 2`, expected `ssemu-object 1`. ".to_string()));
+    assert_eq!(Object::parse("ssemu-object 1\nword x"), Err("Invalid word `x` on line 2. ".to_string()));
+    assert_eq!(Object::parse("ssemu-object 1\nref 0x00 bits"), Err("Invalid field `bits` on line 2. ".to_string()));
+    assert_eq!(Object::parse("ssemu-object 1\nfoo"), Err("Unknown entry `foo` on line 2. ".to_string()));
+    assert_eq!(Object::parse("ssemu-object 1\nword 1\nexport 0x01 x"), Err("Address 0x01 is outside the object's 1 words. ".to_string()));
+    assert_eq!(Object::parse("ssemu-object 1\nword 1\nref 0x00 word x"), Err("The tag `x` is referenced but never imported. ".to_string()));
+}
+
+#[test]
+fn test_link_objects() {
+    let (stack, symbols) = link_objects(&objects()).ok().unwrap();
+    assert_eq!(stack[0..8], [
+        BabyInstruction::Negate(4).to_number(),
+        BabyInstruction::Jump(3).to_number(),
+        BabyInstruction::Stop.to_number(),
+        6,
+        5,
+        1,
+        BabyInstruction::Subtract(5).to_number(),
+        BabyInstruction::Stop.to_number(),
+    ]);
+    assert_eq!(stack[8..], [0; MEMORY_WORDS - 8]);
+    assert_eq!(symbols.serialise(), format!("0x00 start\n0x05 one\n0x06 double\n"));
+}
+
+#[test]
+fn test_link_objects_errors() {
+    let mut objects = objects();
+    objects.push((PathBuf::from("dup.obj"), object_of("%export one\n%import half\n:one\n    abs 0d1").ok().unwrap()));
+    let res = link_objects(&objects).err().unwrap();
+    assert_eq!(res, format!(
+        "error: the tag `one` is exported by both `lib.obj` and `dup.obj`\n\n\
+        error: the tag `half` imported by `dup.obj` isn't exported by any object\n\n\
+        2 errors found. "
+    ));
+
+    let big = object_of(&"    stp\n".repeat(30)).ok().unwrap();
+    let res = link_objects(&[(PathBuf::from("big.obj"), big), objects.remove(1)]).err().unwrap();
+    assert_eq!(res, format!(
+        "error: the linked program is 33 words long, the store is only 32 words\n  \
+        = note: `big.obj` is 30 words, placed at 0x00\n  \
+        = note: `lib.obj` is 3 words, placed at 0x1e\n\n\
+        1 error found. "
+    ));
+}
+
+#[test]
+fn test_link() {
+    let conf = Link { 
+        inputs: vec![PathBuf::from("lib.obj")], 
+        output: None, 
+        format: OutputFormat::Bin, 
+        endian: Endianness::Big 
+    };
+    let lib = object_of("    ldn $one\n    stp\n:one\n    abs 0d1").ok().unwrap();
+    let mut test_int = TestInterface::new_logger_test("Linked 1 object into 3 of 32 store lines. ", "", "");
+    test_int.string_files.insert(PathBuf::from("lib.obj"), lib.serialise());
+    test_int.should_write_addr = PathBuf::from("lib.obj.bin");
+    let mut stack = [0; MEMORY_WORDS];
+    stack[0..3].copy_from_slice(&[BabyInstruction::Negate(2).to_number(), BabyInstruction::Stop.to_number(), 1]);
+    test_int.should_write_data = format_data(Vec::from(stack), Endianness::Big, false);
+    assert!(link(conf, &test_int).is_ok());
+
+    let test_int = TestInterface::new_logger_test("", "", "");
+    match link(Link { inputs: vec![PathBuf::from("nope.obj")], output: None, format: OutputFormat::Bin, endian: Endianness::Big }, &test_int) {
+        Err(e) => assert_eq!(e.exit_code(), crate::errors::EXIT_FILE_ERROR),
+        Ok(_) => panic!()
+    }
+}
@@ -0,0 +1,187 @@
+use baby_emulator::assembler::parser::{parse_line, LineType};
+use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
+use crate::args::Lint;
+use crate::errors::Errors;
+use crate::interface::Interface;
+use crate::run::ProgramStack;
+use crate::assemble::get_src_from_asm;
+use crate::assemble::preprocess::read_source;
+use crate::assemble::listing::{SourceLine, map_preprocessed};
+use crate::notation::describe_instruction;
+use crate::disassemble::analysis::{
+    decode, reachable, find_code, find_operands, find_jump_targets, find_store_targets, jump_target, store_target
+};
+
+#[cfg(test)]
+mod tests;
+
+
+/// A likely mistake found in a program. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Finding {
+    /// The store address the finding is about, none if it's about the whole program. 
+    pub addr: Option<usize>,
+    /// What was found. 
+    pub message: String,
+}
+
+impl Finding {
+    /// Creates a finding about a store address. 
+    /// 
+    /// # Parameters 
+    /// * `addr` - The store address. 
+    /// * `message` - What was found. 
+    /// 
+    pub fn at(addr: usize, message: String) -> Finding {
+        Finding { addr: Some(addr), message }
+    }
+}
+
+/// Determines if a store word is data, either an `abs` line in the source or 
+/// past the end of the source (an empty store line). 
+/// 
+/// # Parameters 
+/// * `addr` - The store address. 
+/// * `sources` - The source lines that produced each store word. 
+/// 
+fn is_data(addr: usize, sources: &[SourceLine]) -> bool {
+    match sources.get(addr) {
+        Some(s) => matches!(parse_line(&s.text, false), Ok(LineType::Absolute(_))),
+        None => true,
+    }
+}
+
+/// Finds the address of the pointer a jump instruction reads its target from, 
+/// if the instruction is a jump. 
+/// 
+/// # Parameters 
+/// * `word` - The instruction word. 
+/// 
+fn jump_pointer(word: i32) -> Option<usize> {
+    match decode(word) {
+        Some(BabyInstruction::Jump(p)) | Some(BabyInstruction::RelativeJump(p)) => Some(p as usize),
+        _ => None
+    }
+}
+
+/// Analyses an assembled program for likely mistakes. 
+/// 
+/// Checks for source lines that are never executed or referenced, execution 
+/// reaching data, stores overwriting instructions, jumps through pointers 
+/// that are never initialised, jumps to store line 0 and no reachable `STP`. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be analysed. 
+/// * `sources` - The source lines that produced each store word. 
+/// 
+pub fn lint_stack(stack: &ProgramStack, sources: &[SourceLine]) -> Vec<Finding> {
+    let reached = reachable(stack);
+    let code = find_code(stack);
+    let operands = find_operands(stack, &code);
+    let stored = find_store_targets(stack, &code);
+    let mut res = vec![];
+
+    for i in 0..sources.len().min(MEMORY_WORDS) {
+        if !reached[i] && !operands[i] && !is_data(i, sources) {
+            res.push(Finding::at(i, "this instruction is never executed".to_string()));
+        }
+    }
+
+    for i in 0..MEMORY_WORDS {
+        let continues = i > 0 && reached[i - 1] && is_data(i - 1, sources);
+        if reached[i] && is_data(i, sources) && !continues {
+            let message = if i >= sources.len() { "execution can run past the end of the program into empty store lines".to_string() }
+                else { "execution can reach this data word".to_string() };
+            res.push(Finding::at(i, message));
+        }
+    }
+
+    for i in (0..MEMORY_WORDS).filter(|i| code[*i]) {
+        if let Some(t) = store_target(i, stack) {
+            if code[t] && !is_data(t, sources) {
+                res.push(Finding::at(i, format!("this store overwrites the instruction at {:#04x}", t)));
+            }
+        }
+    }
+
+    let mut uninitialised = [false; MEMORY_WORDS];
+    for i in (0..MEMORY_WORDS).filter(|i| code[*i]) {
+        let pointer = match jump_pointer(stack[i]) {
+            Some(p) => p,
+            None => continue,
+        };
+        if stack[pointer] == 0 && pointer >= sources.len() && !stored[pointer] {
+            uninitialised[i] = true;
+            res.push(Finding::at(i, format!("this jump reads its target from {:#04x}, which is never initialised", pointer)));
+        }
+    }
+
+    let targets = find_jump_targets(stack, &code);
+    if targets[0] {
+        (0..MEMORY_WORDS)
+            .filter(|i| code[*i] && !uninitialised[*i] && jump_target(*i, stack) == Some(0))
+            .for_each(|i| res.push(Finding::at(i, "this jump goes to store line 0, restarting the program".to_string())));
+    }
+
+    if !(0..MEMORY_WORDS).any(|i| code[i] && decode(stack[i]) == Some(BabyInstruction::Stop)) {
+        res.push(Finding { addr: None, message: "there is no reachable STP, the program never stops".to_string() });
+    }
+
+    res.sort_by_key(|v| v.addr.unwrap_or(MEMORY_WORDS));
+    res
+}
+
+/// Formats a finding with its address and, if there is one, the source line 
+/// that produced the word. 
+/// 
+/// # Parameters 
+/// * `finding` - The finding to be formatted. 
+/// * `stack` - The program stack that was analysed. 
+/// * `sources` - The source lines that produced each store word. 
+/// 
+pub fn format_finding(finding: &Finding, stack: &ProgramStack, sources: &[SourceLine]) -> String {
+    let addr = match finding.addr {
+        Some(v) => v,
+        None => return format!("warning: {}. ", finding.message),
+    };
+    match sources.get(addr) {
+        Some(s) => match &s.origin {
+            Some(o) => format!("warning: {:#04x}: {}. \n  line {}: {}  ({})", addr, finding.message, s.line, s.text, o),
+            None => format!("warning: {:#04x}: {}. \n  line {}: {}", addr, finding.message, s.line, s.text),
+        },
+        None => format!(
+            "warning: {:#04x}: {}. \n  {}", addr, finding.message,
+            describe_instruction(&BabyInstruction::from_number(stack[addr] as u16))
+        ),
+    }
+}
+
+/// Assembles an asm source file from an interface, analyses it for likely 
+/// mistakes and reports each one as a warning. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to be used. 
+/// * `interface` - The interface to be used for reading & reporting. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The source was assembled and analysed. 
+/// * [Err(Errors)] - The source couldn't be read or assembled. 
+/// 
+pub fn lint(conf: Lint, interface: &impl Interface) -> Result<(), Errors> {
+    let stack = get_src_from_asm(&conf.input, conf.og_notation, interface)
+        .map_err(Errors::AsmError)?;
+
+    let pre = read_source(&conf.input, interface)
+        .map_err(Errors::AsmError)?;
+    let sources = map_preprocessed(&pre, &conf.input, conf.og_notation);
+
+    let findings = lint_stack(&stack, &sources);
+    findings.iter().for_each(|f| interface.log_warn(format_finding(f, &stack, &sources)));
+
+    let count = findings.len();
+    interface.log_msg(match count {
+        0 => "No problems found. ".to_string(),
+        _ => format!("{} warning{} found. ", count, if count == 1 { "" } else { "s" }),
+    });
+    Ok(())
+}
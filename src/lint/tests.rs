@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+use super::*;
+use baby_emulator::assembler::assemble;
+use crate::test_utils::TestInterface;
+use crate::assemble::listing::map_source;
+
+
+fn lint_asm(asm: &str) -> Vec<Finding> {
+    let asm = asm.to_owned();
+    let stack = BabyInstruction::to_numbers(assemble(&asm, false).ok().unwrap());
+    lint_stack(&stack, &map_source(&asm, false))
+}
+
+#[test]
+fn test_lint_clean() {
+    assert_eq!(lint_asm("ldn $x\nsub $x\nstp\n:x\nabs 0d1"), vec![]);
+}
+
+#[test]
+fn test_lint_unreachable() {
+    assert_eq!(
+        lint_asm("jmp $p\nsto $x\n:end\nstp\n:p\nabs $end\n:x\nabs 0d0"),
+        vec![Finding::at(1, "this instruction is never executed".to_string())]
+    );
+}
+
+#[test]
+fn test_lint_falls_into_data() {
+    assert_eq!(
+        lint_asm("ldn $x\n:x\nabs 0d57344"),
+        vec![Finding::at(1, "execution can reach this data word".to_string())]
+    );
+    assert_eq!(
+        lint_asm("ldn $x\ncmp\nstp\nsub $x\n:x\nabs 0d-1"),
+        vec![Finding::at(4, "execution can reach this data word".to_string())]
+    );
+    let res = lint_asm("ldn 0x1f");
+    assert_eq!(res[0], Finding::at(1, "execution can run past the end of the program into empty store lines".to_string()));
+}
+
+#[test]
+fn test_lint_self_modification() {
+    assert_eq!(
+        lint_asm(":start\nldn $x\nsto $p\nstp\n:x\nabs 0d1\n:p\nabs $start"),
+        vec![Finding::at(1, "this store overwrites the instruction at 0x00".to_string())]
+    );
+}
+
+#[test]
+fn test_lint_jumps() {
+    assert_eq!(
+        lint_asm("cmp\njmp 0x1f\nstp"),
+        vec![Finding::at(1, "this jump reads its target from 0x1f, which is never initialised".to_string())]
+    );
+    assert_eq!(
+        lint_asm("sto $p\ncmp\njmp 0x1f\nstp\n:p\nabs 0d31"),
+        vec![Finding::at(2, "this jump goes to store line 0, restarting the program".to_string())]
+    );
+}
+
+#[test]
+fn test_lint_no_stop() {
+    assert_eq!(lint_asm("ldn $x\njmp $p\n:x\nabs 0d1\n:p\nabs 0d0"), vec![
+        Finding::at(1, "this jump goes to store line 0, restarting the program".to_string()),
+        Finding { addr: None, message: "there is no reachable STP, the program never stops".to_string() },
+    ]);
+}
+
+#[test]
+fn test_format_finding() {
+    let asm = "stp\n:x\nabs 0d1".to_string();
+    let stack = BabyInstruction::to_numbers(assemble(&asm, false).ok().unwrap());
+    let sources = map_source(&asm, false);
+    assert_eq!(
+        format_finding(&Finding::at(1, "foo".to_string()), &stack, &sources),
+        format!("warning: 0x01: foo. \n  line 3: abs 0d1")
+    );
+    assert_eq!(format_finding(&Finding::at(4, "foo".to_string()), &stack, &sources), format!("warning: 0x04: foo. \n  JMP 0x00"));
+    assert_eq!(format_finding(&Finding { addr: None, message: "foo".to_string() }, &stack, &sources), format!("warning: foo. "));
+}
+
+#[test]
+fn test_lint() {
+    let mut test_int = TestInterface::new_logger_test("No problems found. ", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "stp".to_string());
+    assert!(lint(Lint { input: PathBuf::from("foo.asm"), og_notation: false }, &test_int).is_ok());
+
+    let mut test_int = TestInterface::new_logger_test("1 warning found. ", "warning: 0x01: this instruction is never executed. \n  line 2: Test", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), "Stop\nTest".to_string());
+    assert!(lint(Lint { input: PathBuf::from("foo.asm"), og_notation: true }, &test_int).is_ok());
+
+    let test_int = TestInterface::new_logger_test("", "", "");
+    assert!(lint(Lint { input: PathBuf::from("foo.asm"), og_notation: false }, &test_int).is_err());
+}
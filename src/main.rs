@@ -2,11 +2,18 @@ use clap::Parser;
 use colored::Colorize;
 use args::{Commands, Cli};
 use interface::Interface;
-use run::execute;
+use run::{execute, resume};
 use assemble::assemble;
+use disassemble::disassemble;
+use lint::lint;
+use formatter::fmt;
+use convert::convert;
+use link::link;
 
 /// Contains functionality for assembling a source file. 
 mod assemble;
+/// Contains functionality for disassembling a binary file. 
+mod disassemble;
 /// Errors thrown during runnig the program. 
 mod errors;
 /// Contains functionality for interacting with the user/host system. 
@@ -15,6 +22,26 @@ mod interface;
 mod args;
 /// Contains functionality for running a program. 
 mod run;
+/// Contains functionality for analysing a program for likely mistakes. 
+mod lint;
+/// Contains functionality for rewriting asm source in the canonical layout. 
+mod formatter;
+/// Contains functionality for converting asm source between notations. 
+mod convert;
+/// Contains functionality for linking relocatable object files. 
+mod link;
+/// Contains helpers for writing instructions in asm notation. 
+mod notation;
+/// Contains the symbol table mapping tag names to store addresses. 
+mod symbols;
+/// Contains functionality for reading and writing `.snp` files. 
+mod snp;
+/// Contains the versioned program image container. 
+mod image;
+/// Contains the relocatable object format produced by `assemble --object`. 
+mod object;
+/// Contains the library of routines bundled into the binary. 
+mod stdlib;
 #[cfg(test)]
 pub mod test_utils;
 
@@ -27,6 +54,12 @@ fn main() {
     let res = match cli.command {
         Commands::Run(v) => execute(v, &int),
         Commands::Assemble(a) => assemble(a, &int),
+        Commands::Resume(v) => resume(v, &int),
+        Commands::Disassemble(d) => disassemble(d, &int),
+        Commands::Lint(l) => lint(l, &int),
+        Commands::Fmt(f) => fmt(f, &int),
+        Commands::Convert(c) => convert(c, &int),
+        Commands::Link(l) => link(l, &int),
     };
 
     match res {
@@ -34,6 +67,7 @@ fn main() {
         Err(e) => {
             int.log_error("Error".to_owned());
             int.log_warn(e.describe());
+            std::process::exit(e.exit_code());
         },
     }
     
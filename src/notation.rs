@@ -0,0 +1,66 @@
+use baby_emulator::core::instructions::BabyInstruction;
+
+
+/// Gets the modern notation asm mnemonic for an instruction. 
+/// 
+/// # Parameters 
+/// * `instruction` - The instruction to get the mnemonic for. 
+/// 
+pub fn mnemonic(instruction: &BabyInstruction) -> &'static str {
+    match instruction {
+        BabyInstruction::Jump(_) => "JMP",
+        BabyInstruction::RelativeJump(_) => "JRP",
+        BabyInstruction::Negate(_) => "LDN",
+        BabyInstruction::Store(_) => "STO",
+        BabyInstruction::Subtract(_) => "SUB",
+        BabyInstruction::SkipNextIfNegative => "CMP",
+        BabyInstruction::Stop => "STP",
+        BabyInstruction::AbsoluteValue(_) => "ABS",
+    }
+}
+
+/// Determines if an instruction takes a memory address operand. 
+/// 
+/// # Parameters 
+/// * `instruction` - The instruction to check. 
+/// 
+pub fn has_operand(instruction: &BabyInstruction) -> bool {
+    !matches!(instruction, BabyInstruction::SkipNextIfNegative | BabyInstruction::Stop)
+}
+
+/// Writes an instruction as an asm source line. 
+/// 
+/// # Parameters 
+/// * `instruction` - The instruction to be written. 
+/// * `operand` - The operand as it should appear in source, e.g. `0x1f` or `$tag`. 
+/// * `og_notation` - Whether to write in original notation. 
+/// 
+pub fn write_instruction(instruction: &BabyInstruction, operand: &str, og_notation: bool) -> String {
+    if og_notation {
+        match instruction {
+            BabyInstruction::Jump(_) => format!("{}, Cl", operand),
+            BabyInstruction::RelativeJump(_) => format!("Add {}, Cl", operand),
+            BabyInstruction::Negate(_) => format!("-{}, C", operand),
+            BabyInstruction::Store(_) => format!("c, {}", operand),
+            BabyInstruction::Subtract(_) => format!("SUB {}", operand),
+            BabyInstruction::SkipNextIfNegative => "Test".to_string(),
+            BabyInstruction::Stop => "Stop".to_string(),
+            BabyInstruction::AbsoluteValue(_) => format!("abs {}", operand),
+        }
+    } else if has_operand(instruction) {
+        format!("{} {}", mnemonic(instruction).to_lowercase(), operand)
+    } else { mnemonic(instruction).to_lowercase() }
+}
+
+/// Describes an instruction as its mnemonic and operand, e.g. `LDN 0x07` or `ABS -10`. 
+/// 
+/// # Parameters 
+/// * `instruction` - The instruction to be described. 
+/// 
+pub fn describe_instruction(instruction: &BabyInstruction) -> String {
+    match instruction {
+        BabyInstruction::AbsoluteValue(v) => format!("{} {}", mnemonic(instruction), v),
+        i if has_operand(i) => format!("{} {:#04x}", mnemonic(i), i.get_operand()),
+        i => mnemonic(i).to_owned(),
+    }
+}
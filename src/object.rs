@@ -0,0 +1,159 @@
+use baby_emulator::core::MEMORY_WORDS;
+
+
+/// The first line of every object file, identifying the format. 
+pub const OBJECT_MAGIC: &str = "ssemu-object";
+
+/// The version of the object format written. 
+pub const OBJECT_VERSION: u16 = 1;
+
+/// The file extension of object files. 
+pub const OBJECT_EXTENSION: &str = "obj";
+
+/// The part of a word a reference fills in with an address. 
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Field {
+    /// The operand of an instruction, the lowest 5 bits. 
+    Operand,
+    /// The whole word, an `abs` value. 
+    Word,
+}
+
+impl Field {
+    /// Gets the name the field is serialised as. 
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Operand => "operand",
+            Field::Word => "word",
+        }
+    }
+
+    /// Gets the address currently held in the field of a word. 
+    /// 
+    /// # Parameters 
+    /// * `word` - The word. 
+    /// 
+    pub fn get(&self, word: i32) -> i32 {
+        match self {
+            Field::Operand => word & 0x1F,
+            Field::Word => word,
+        }
+    }
+
+    /// Replaces the address held in the field of a word. 
+    /// 
+    /// # Parameters 
+    /// * `word` - The word. 
+    /// * `addr` - The address to be written. 
+    /// 
+    pub fn set(&self, word: i32, addr: i32) -> i32 {
+        match self {
+            Field::Operand => (word & !0x1F) | (addr & 0x1F),
+            Field::Word => addr,
+        }
+    }
+}
+
+/// A word in an object holding the address of a tag, to be filled in when linking. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Reference {
+    /// The address of the word within the object. 
+    pub addr: usize,
+    /// The part of the word holding the address. 
+    pub field: Field,
+    /// The imported tag referenced, none if the tag is in the same object, in 
+    /// which case the word holds its address within the object. 
+    pub symbol: Option<String>,
+}
+
+/// A relocatable object, assembled as if placed at store line 0, along with 
+/// the tags it exports & imports and every word referencing a tag. 
+/// 
+/// Serialised as text, the magic & version on the first line, then one entry 
+/// per line: `word value` for each word in order, `export address name`, 
+/// `import name` and `ref address field [name]`. 
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Object {
+    /// The words, references to imported tags hold 0. 
+    pub words: Vec<i32>,
+    /// The exported tag names and their addresses within the object. 
+    pub exports: Vec<(String, usize)>,
+    /// The imported tag names. 
+    pub imports: Vec<String>,
+    /// The words referencing tags. 
+    pub references: Vec<Reference>,
+}
+
+impl Object {
+    /// Serialises the object to be saved to a file. 
+    pub fn serialise(&self) -> String {
+        let mut res = format!("{} {}\n", OBJECT_MAGIC, OBJECT_VERSION);
+        self.words.iter().for_each(|v| res += &format!("word {}\n", v));
+        self.exports.iter().for_each(|(n, a)| res += &format!("export {:#04x} {}\n", a, n));
+        self.imports.iter().for_each(|n| res += &format!("import {}\n", n));
+        for r in &self.references {
+            res += &match &r.symbol {
+                Some(s) => format!("ref {:#04x} {} {}\n", r.addr, r.field.name(), s),
+                None => format!("ref {:#04x} {}\n", r.addr, r.field.name()),
+            };
+        }
+        res
+    }
+
+    /// Parses an object serialised by [Object::serialise]. 
+    /// 
+    /// # Parameters 
+    /// * `src` - The serialised object. 
+    /// 
+    /// # Returns 
+    /// * [Ok(Object)] - The parsed object. 
+    /// * [Err(String)] - A message describing what's wrong with the object. 
+    /// 
+    pub fn parse(src: &str) -> Result<Object, String> {
+        let mut lines = src.lines().enumerate();
+        let expected = format!("{} {}", OBJECT_MAGIC, OBJECT_VERSION);
+        match lines.next() {
+            Some((_, v)) if v.trim() == expected => (),
+            Some((_, v)) if v.starts_with(OBJECT_MAGIC) =>
+                return Err(format!("Unsupported object `{}`, expected `{}`. ", v.trim(), expected)),
+            _ => return Err(format!("Missing magic `{}`. ", OBJECT_MAGIC)),
+        }
+
+        let mut res = Object::default();
+        for (i, line) in lines {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let addr = |v: &str| usize::from_str_radix(v.trim_start_matches("0x"), 16).ok()
+                .ok_or(format!("Invalid address `{}` on line {}. ", v, i + 1));
+            match words.as_slice() {
+                [] => (),
+                ["word", v] => res.words.push(v.parse::<i32>()
+                    .map_err(|_| format!("Invalid word `{}` on line {}. ", v, i + 1))?),
+                ["export", a, n] => res.exports.push((n.to_string(), addr(a)?)),
+                ["import", n] => res.imports.push(n.to_string()),
+                ["ref", a, f, n @ ..] if n.len() < 2 => {
+                    let field = match *f {
+                        "operand" => Field::Operand,
+                        "word" => Field::Word,
+                        _ => return Err(format!("Invalid field `{}` on line {}. ", f, i + 1)),
+                    };
+                    res.references.push(Reference { addr: addr(a)?, field, symbol: n.first().map(|v| v.to_string()) });
+                },
+                _ => return Err(format!("Unknown entry `{}` on line {}. ", line.trim(), i + 1)),
+            }
+        }
+
+        if res.words.len() > MEMORY_WORDS {
+            return Err(format!("The object is {} words long, the store is only {} words. ", res.words.len(), MEMORY_WORDS));
+        }
+        let outside = res.exports.iter().map(|(_, a)| *a)
+            .chain(res.references.iter().map(|r| r.addr))
+            .find(|a| *a >= res.words.len());
+        if let Some(a) = outside {
+            return Err(format!("Address {:#04x} is outside the object's {} words. ", a, res.words.len()));
+        }
+        if let Some(s) = res.references.iter().filter_map(|r| r.symbol.as_ref()).find(|s| !res.imports.contains(s)) {
+            return Err(format!("The tag `{}` is referenced but never imported. ", s));
+        }
+        Ok(res)
+    }
+}
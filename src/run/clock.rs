@@ -0,0 +1,55 @@
+use std::time::Duration;
+use baby_emulator::core::instructions::BabyInstruction;
+
+
+/// The number of scan beats in an instruction, where the instruction is fetched. 
+pub const SCAN_BEATS: usize = 2;
+
+/// The number of action beats in an instruction, where the instruction is executed. 
+pub const ACTION_BEATS: usize = 2;
+
+/// The length of a single beat on the original machine. 
+pub const BEAT_DURATION: Duration = Duration::from_micros(360);
+
+/// Gets the number of beats the original machine took to execute an instruction. 
+/// 
+/// The Baby was synchronous, every instruction took a full bar of scan & action beats 
+/// regardless of what it did. 
+/// 
+/// # Parameters 
+/// * `_instruction` - The executed instruction. 
+/// 
+pub fn instruction_beats(_instruction: &BabyInstruction) -> usize {
+    SCAN_BEATS + ACTION_BEATS
+}
+
+/// Counts executed instructions and the machine beats they took. 
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Clock {
+    /// The number of instructions executed. 
+    pub steps: usize,
+    /// The number of beats taken by the executed instructions. 
+    pub beats: usize,
+}
+
+impl Clock {
+    /// Creates a new clock with nothing executed. 
+    pub fn new() -> Clock {
+        Clock { steps: 0, beats: 0 }
+    }
+
+    /// Advances the clock by an executed instruction. 
+    /// 
+    /// # Parameters 
+    /// * `instruction` - The executed instruction. 
+    /// 
+    pub fn tick(&mut self, instruction: &BabyInstruction) {
+        self.steps += 1;
+        self.beats += instruction_beats(instruction);
+    }
+
+    /// Gets the time the original machine took to execute up to this point. 
+    pub fn time(&self) -> Duration {
+        Duration::from_micros(BEAT_DURATION.as_micros() as u64 * self.beats as u64)
+    }
+}
@@ -1,6 +1,10 @@
+use std::path::PathBuf;
 use baby_emulator::core::BabyModel;
 use crate::args::Run;
 use crate::interface::Interface;
+use crate::run::errors::RunError;
+use crate::run::snapshot::save_snapshot;
+use crate::run::state::RunState;
 use super::print::print;
 use super::modify::modify;
 
@@ -12,10 +16,34 @@ pub const HELP: &str =
 p, print - Print the value of a register, memory location(s) (use `print help`). 
 s, set - Set a memory locations, registers, breakpoints, or memorylocation/register to print on debug (use `set help`). 
 n, next - Perform the next instruction and debug. 
+b, back [N] - Step back 1 (or N) instructions. 
+rc, reverse-continue - Step back to the last time a breakpoint was hit. 
+save file - Save a snapshot of the model and debug configuration to resume from later. 
 c, continue - Continue execution. 
 e, end - End execution. 
 h, help - Print this help command";
 
+/// Saves a snapshot of the model and configuration to a file, printing the outcome. 
+/// 
+/// # Parameters 
+/// * `path` - The path of the file to save to. 
+/// * `conf` - The configuration model to be saved. 
+/// * `model` - The model to be saved. 
+/// * `state` - The runtime state holding the clock & symbols to be saved. 
+/// * `int` - The interface to write to and print messages. 
+/// 
+pub fn save(path: &str, conf: &Run, model: &BabyModel, state: &RunState, int: &impl Interface) {
+    let path = path.trim();
+    if path.is_empty() {
+        int.log_warn("Please specify a file to save the snapshot to. ".to_string());
+        return;
+    }
+    match save_snapshot(&PathBuf::from(path), model, state, conf, int) {
+        Ok(_) => int.log_msg(format!("Saved snapshot to `{}`. ", path)),
+        Err(e) => int.log_warn(e.describe())
+    }
+}
+
 /// Finds a matching debug command and dispatches the relevant actions. 
 /// 
 /// Returns the model and configuration containing any changes. 
@@ -24,26 +52,33 @@ h, help - Print this help command";
 /// * `command` - The string command stating what is being set. 
 /// * `model` - The model to be acted upon. 
 /// * `conf` - The configuration model to be acted upon. 
+/// * `state` - The runtime state to be read. 
 /// * `int` - The interface to print messages. 
 /// 
 pub fn match_debug_command(
     command: String, 
     conf: &Run, 
     model: &BabyModel, 
+    state: &RunState,
     int: &impl Interface
 ) -> (BabyModel, Run) {
-    let command = command.trim().to_lowercase();
+    let raw_command = command.trim().to_owned();
+    let command = raw_command.to_lowercase();
     let (next_com, _) = command.split_at(command.find(" ").unwrap_or(command.len()));
     let next_com = next_com.trim();
     match next_com {
-        "s" | "set" => modify(command.replace("set", ""), conf, model, int),
+        "save" => {
+            save(&raw_command[next_com.len()..], conf, model, state, int);
+            (model.clone(), conf.clone())
+        },
+        "s" | "set" => modify(command[next_com.len()..].to_owned(), conf, model, &state.symbols, int),
         "p" | "print" => {
-            print(command.replace("print", ""), conf, model, int);
+            print(command[next_com.len()..].to_owned(), conf, model, state, int);
             (model.clone(), conf.clone())
         },
 
         "" | "h" | "help" => {
-            int.log_msg(format!("{}", HELP));
+            int.log_msg(HELP.to_string());
             (model.clone(), conf.clone())
         },
         
@@ -3,7 +3,10 @@ use colored::Colorize;
 use crate::args::Run;
 use crate::interface::Interface;
 use super::output::output_model;
+use super::display::output_display;
+use super::state::RunState;
 use commands::match_debug_command;
+use rewind::{step_back, reverse_continue};
 
 /// Contains helpers for parsing/actioning debug commands. 
 pub mod commands;
@@ -13,6 +16,10 @@ pub mod print;
 pub mod utils;
 /// Contains helpers for parsing/actioning modifying debug commands. 
 pub mod modify;
+/// Contains helpers for actioning rewinding debug commands. 
+pub mod rewind;
+/// Contains the full-screen terminal UI for debug sessions. 
+pub mod tui;
 #[cfg(test)]
 mod tests;
 
@@ -28,6 +35,63 @@ pub enum DebugResult {
     End(BabyModel, Run)
 }
 
+/// The result of a single debug command, either the session ends with a 
+/// [DebugResult] or it carries on with any changes applied. 
+pub enum CommandResult {
+    /// End the session. 
+    Exit(DebugResult),
+    /// Carry on the session with the updated model and configuration. 
+    Stay(BabyModel, Run),
+}
+
+/// Actions a single line of debug input. 
+/// 
+/// # Parameters 
+/// * `raw_line` - The line of input from the user. 
+/// * `model` - The simulation model to act upon. 
+/// * `conf` - The configuration model to act upon. 
+/// * `state` - The runtime state, rewound by stepping back. 
+/// * `int` - The interface used to i/o by the command. 
+/// 
+pub fn debug_command(
+    raw_line: String,
+    model: BabyModel, 
+    conf: Run, 
+    state: &mut RunState, 
+    int: &impl Interface
+) -> CommandResult {
+    let raw_line = raw_line.trim().to_owned();
+    let line = raw_line.to_lowercase();
+
+    if line.starts_with("continue") || line.starts_with("c") 
+        { return CommandResult::Exit(DebugResult::Continue(model, conf)) }
+    if line.starts_with("next") || line.starts_with("n") 
+        { return CommandResult::Exit(DebugResult::SingleStep(model, conf)) } 
+    if line.starts_with("end") || line.starts_with("e") 
+        { return CommandResult::Exit(DebugResult::End(model, conf)) } 
+
+    let (next_com, args) = line.split_at(line.find(" ").unwrap_or(line.len()));
+    let rewound = match next_com {
+        "b" | "back" => Some(step_back(args.to_owned(), state)),
+        "rc" | "reverse-continue" => Some(reverse_continue(&conf, state)),
+        _ => None
+    };
+
+    match rewound {
+        Some(Ok(m)) => {
+            int.log_msg(format!("Rewound to step {}. ", state.clock.steps));
+            output_model(&conf.output_regs, &conf.output_addr, conf.output_model, &m, state, int);
+            if conf.display { output_display(&m, int); }
+            CommandResult::Stay(m, conf)
+        },
+        Some(Err(e)) => { int.log_warn(e); CommandResult::Stay(model, conf) },
+        None => {
+            let (model, conf) = match_debug_command(raw_line, &conf, &model, state, int);
+            CommandResult::Stay(model, conf)
+        }
+    }
+}
+
 /// Runs a debug session loop for the user for a givern model and configuration. 
 /// 
 /// Returns the model and config with any changes applied. 
@@ -35,28 +99,25 @@ pub enum DebugResult {
 /// # Parameters 
 /// * `model` - The simulation model to run aganst. 
 /// * `conf` - The configuration model to run against. 
+/// * `state` - The runtime state, rewound by stepping back. 
 /// * `int` - The interface used to i/o by the debug session. 
 /// 
-pub fn check_debug_session(model: &BabyModel, conf: &Run, int: &impl Interface) -> DebugResult {
+pub fn check_debug_session(
+    model: &BabyModel, 
+    conf: &Run, 
+    state: &mut RunState, 
+    int: &impl Interface
+) -> DebugResult {
     let (mut model, mut conf) = (model.clone(), conf.clone());
     int.log_msg(format!("{}", "Debug".cyan()));
-    output_model(&conf.output_regs, &conf.output_addr, conf.output_model, &model, int);
+    output_model(&conf.output_regs, &conf.output_addr, conf.output_model, &model, state, int);
+    if conf.display { output_display(&model, int); }
     loop {
-        int.log_inline(format!("(ssemu-debug) "));
+        int.log_inline("(ssemu-debug) ".to_string());
 
-        let line = int.get_line()
-            .to_lowercase()
-            .trim()
-            .to_owned();
-
-        if line.starts_with("continue") || line.starts_with("c") { break; }
-        if line.starts_with("next") || line.starts_with("n") 
-            { return DebugResult::SingleStep(model, conf) } 
-        if line.starts_with("end") || line.starts_with("e") 
-            { return DebugResult::End(model, conf) } 
-
-        (model, conf) = match_debug_command(line, &conf, &model, int);
+        (model, conf) = match debug_command(int.get_line(), model, conf, state, int) {
+            CommandResult::Exit(res) => return res,
+            CommandResult::Stay(m, c) => (m, c),
+        };
     }
-
-    DebugResult::Continue(model, conf)
 }
@@ -1,7 +1,8 @@
 use baby_emulator::core::BabyModel;
 use crate::args::{Run, Registers};
 use crate::interface::Interface;
-use super::utils::{parse_register, parse_memory_value, parse_instruction, parse_memory_address};
+use crate::symbols::Symbols;
+use super::utils::{parse_register, parse_memory_value, parse_instruction, resolve_memory_address};
 
 
 /// The help message printed for a list of set commands. 
@@ -10,6 +11,7 @@ pub const SET_HELP_MSG: &str =
 
 reg accumulator/instruction/instructionaddress 0x10 - Set a register to a given value. 
 mem 0x10 0x10 - Set a memory location to a given value (address value). 
+Addresses can also be tag names, e.g. `break-addrs + loop`. 
 debug-addrs +/- 0x10 - Add/remove a memory address to print on debug. 
 break-addrs +/- 0x10 - Add/remove a memory address to debug when hit. 
 debug-regs +/- accumulator/instruction/instructionaddress - Add/remove a register to print on debug. 
@@ -27,11 +29,11 @@ h, help - Print this help message. ";
 /// * [Ok(BabyModel)] - If the parsing and setting has happened correctly. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn set_instruction_reg(value: &String, address: bool, model: &BabyModel) -> Result<BabyModel, String> {
+pub fn set_instruction_reg(value: &str, address: bool, model: &BabyModel) -> Result<BabyModel, String> {
     let value = value.trim();
     let value = parse_instruction(value).map_err(|e| format!("Invalid value: `{}`. ", e))?;
     Ok(BabyModel {
-        main_store: model.main_store.clone(),
+        main_store: model.main_store,
         accumulator: model.accumulator,
         instruction_address: if address { value } else { model.instruction_address },
         instruction: if !address { value } else { model.instruction },
@@ -48,11 +50,11 @@ pub fn set_instruction_reg(value: &String, address: bool, model: &BabyModel) ->
 /// * [Ok(BabyModel)] - If the parsing and setting suceeded. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn set_accumulator(value: &String, model: &BabyModel) -> Result<BabyModel, String> {
+pub fn set_accumulator(value: &str, model: &BabyModel) -> Result<BabyModel, String> {
     let value = value.trim();
     let value = parse_memory_value(value).map_err(|e| format!("Invalid value: `{}`. ", e))?;
     Ok(BabyModel {
-        main_store: model.main_store.clone(),
+        main_store: model.main_store,
         accumulator: value,
         instruction_address: model.instruction_address,
         instruction: model.instruction,
@@ -64,25 +66,30 @@ pub fn set_accumulator(value: &String, model: &BabyModel) -> Result<BabyModel, S
 /// # Parameters 
 /// * `command` - The string command containing the register and the value to set it to. 
 /// * `model` - The model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
 /// 
 /// # Returns 
 /// * [Ok(BabyModel)] - If the parsing and setting suceeded. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn set_register(command: String, model: &BabyModel) -> Result<BabyModel, String> {
+pub fn set_register(command: String, model: &BabyModel, symbols: &Symbols) -> Result<BabyModel, String> {
     let command = command.trim();
 
     let index = if let Some(v) = command.find(" ") { v }
-    else { return Err(format!("Please specify a register and a value. ")); };
+    else { return Err("Please specify a register and a value. ".to_string()); };
 
     let (register, value) = command.split_at(index);
 
-    let register = parse_register(&register)
+    let register = parse_register(register)
         .map_err(|_| format!("No such register `{}`", register))?;
     match register {
-        Registers::Accumulator => set_accumulator(&value.to_owned(), model),
-        Registers::Instruction => set_instruction_reg(&value.to_owned(), false, model),
-        Registers::InstructionAddress => set_instruction_reg(&value.to_owned(), true, model),
+        Registers::Accumulator => set_accumulator(value, model),
+        Registers::Instruction => set_instruction_reg(value, false, model),
+        Registers::InstructionAddress => {
+            let value = symbols.address_of(value).map(|v| v.to_string()).unwrap_or(value.to_owned());
+            set_instruction_reg(&value, true, model)
+        },
+        Registers::Beats => Err("The beats register is read only. ".to_string()),
     }
 }
 
@@ -91,25 +98,26 @@ pub fn set_register(command: String, model: &BabyModel) -> Result<BabyModel, Str
 /// # Parameters 
 /// * `command` - The string command containing the memory location and the value to set it to. 
 /// * `model` - The model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
 /// 
 /// # Returns 
 /// * [Ok(BabyModel)] - If the parsing and setting suceeded. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn set_memory_address(command: String, model: &BabyModel) -> Result<BabyModel, String> {
+pub fn set_memory_address(command: String, model: &BabyModel, symbols: &Symbols) -> Result<BabyModel, String> {
     let command = command.trim();
 
     let index = if let Some(v) = command.find(" ") { v }
-    else { return Err(format!("Please specify a memory address and a value. ")); };
+    else { return Err("Please specify a memory address and a value. ".to_string()); };
 
     let (address, value) = command.split_at(index);
 
-    let address = parse_memory_address(&address)
+    let address = resolve_memory_address(address, symbols)
         .map_err(|e| format!("Invalid memory address: `{}`. ", e))?;
-    let value = parse_memory_value(&value)
+    let value = parse_memory_value(value)
         .map_err(|e| format!("Invalid value: `{}`. ", e))?;
 
-    let mut main_store = model.main_store.clone();
+    let mut main_store = model.main_store;
     main_store[address] = value;
     Ok(BabyModel {
         main_store,
@@ -164,11 +172,11 @@ pub fn add_if_not_present<T: PartialEq + Clone>(value: T, vals: Vec<T>) -> Vec<T
 /// * [Ok(Vec<T>)] - If sucessfully parsed the command string. 
 /// * [Err(String)] - If there was an error parsing the command string, contains an error message. 
 /// 
-pub fn add_or_remove<T: PartialEq + Clone>(action: &String, value: T, vals: Vec<T>) -> Result<Vec<T>, String> {
-    match action.as_str() {
+pub fn add_or_remove<T: PartialEq + Clone>(action: &str, value: T, vals: Vec<T>) -> Result<Vec<T>, String> {
+    match action {
         "+" => Ok(add_if_not_present(value, vals)),
         "-" => Ok(remove_if_present(value, vals)),
-        v => return Err(format!("Invalid action `{}`, actions must be add (+) or remove (-)", v))
+        v => Err(format!("Invalid action `{}`, actions must be add (+) or remove (-)", v))
     }
 }
 
@@ -177,12 +185,13 @@ pub fn add_or_remove<T: PartialEq + Clone>(action: &String, value: T, vals: Vec<
 /// # Parameters 
 /// * `command` - The string command containing the memory location and whether to add or remove. 
 /// * `conf` - The configuration model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
 /// 
 /// # Returns 
 /// * [Ok(Run)] - If the parsing and setting suceeded. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn set_debug_address(command: String, conf: &Run) -> Result<Run, String> {
+pub fn set_debug_address(command: String, conf: &Run, symbols: &Symbols) -> Result<Run, String> {
     let command = command.trim();
 
     let index = if let Some(v) = command.find(" ") { v }
@@ -190,8 +199,8 @@ pub fn set_debug_address(command: String, conf: &Run) -> Result<Run, String> {
 
     let (action, value) = command.split_at(index);
 
-    let address = parse_memory_address(&value).map_err(|e| format!("Invalid memory address `{}`. ", e))?;
-    let output_addr = add_or_remove(&action.to_owned(), address, conf.output_addr.clone()).map_err(|e| e)?;
+    let address = resolve_memory_address(value, symbols).map_err(|e| format!("Invalid memory address `{}`. ", e))?;
+    let output_addr = add_or_remove(action, address, conf.output_addr.clone())?;
     let mut conf = conf.clone();
     conf.output_addr = output_addr;
     Ok(conf)
@@ -203,12 +212,13 @@ pub fn set_debug_address(command: String, conf: &Run) -> Result<Run, String> {
 /// # Parameters 
 /// * `command` - The string command containing the memory location and whether to add or remove. 
 /// * `conf` - The configuration model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
 /// 
 /// # Returns 
 /// * [Ok(Run)] - If the parsing and setting suceeded. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn set_break_address(command: String, conf: &Run) -> Result<Run, String> {
+pub fn set_break_address(command: String, conf: &Run, symbols: &Symbols) -> Result<Run, String> {
     let command = command.trim();
 
     let index = if let Some(v) = command.find(" ") { v }
@@ -216,8 +226,8 @@ pub fn set_break_address(command: String, conf: &Run) -> Result<Run, String> {
 
     let (action, value) = command.split_at(index);
         
-    let address = parse_memory_address(&value).map_err(|e| format!("Invalid memory address `{}`. ", e))?;
-    let break_addr = add_or_remove(&action.to_owned(), address, conf.break_addr.clone()).map_err(|e| e)?;
+    let address = resolve_memory_address(value, symbols).map_err(|e| format!("Invalid memory address `{}`. ", e))?;
+    let break_addr = add_or_remove(action, address, conf.break_addr.clone())?;
     let mut conf = conf.clone();
     conf.break_addr = break_addr;
     Ok(conf)
@@ -241,8 +251,8 @@ pub fn set_debug_regs(command: String, conf: &Run) -> Result<Run, String> {
 
     let (action, value) = command.split_at(index);
 
-    let register = parse_register(&value).map_err(|e| format!("Invalid register name `{}`. ", e))?;
-    let output_regs = add_or_remove(&action.to_owned(), register, conf.output_regs.clone()).map_err(|e| e)?;
+    let register = parse_register(value).map_err(|e| format!("Invalid register name `{}`. ", e))?;
+    let output_regs = add_or_remove(action, register, conf.output_regs.clone())?;
     let mut conf = conf.clone();
     conf.output_regs = output_regs;
     Ok(conf)
@@ -254,21 +264,22 @@ pub fn set_debug_regs(command: String, conf: &Run) -> Result<Run, String> {
 /// # Parameters 
 /// * `command` - The string command stating what is being set (either "reg" or "mem"). 
 /// * `model` - The model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
 /// 
 /// # Returns 
 /// * [Ok(BabyModel)] - If the parsing and setting suceeded. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn parse_set_model(command: &str, model: &BabyModel) -> Result<BabyModel, String> {
+pub fn parse_set_model(command: &str, model: &BabyModel, symbols: &Symbols) -> Result<BabyModel, String> {
     let command = command.trim();
     let (next_com, _) = command.split_at(command.find(" ").unwrap_or(command.len() - 1));
     let next_com = next_com.trim();
     let model = match next_com {
-        "reg" => set_register(command.replace("reg", ""), model).map_err(|e| e)?,
-        "mem" => set_memory_address(command.replace("mem", ""), model).map_err(|e| e)?,
-        _ => return Err(format!("No such option. "))
+        "reg" => set_register(command[next_com.len()..].to_owned(), model, symbols)?,
+        "mem" => set_memory_address(command[next_com.len()..].to_owned(), model, symbols)?,
+        _ => return Err("No such option. ".to_string())
     };
-    return Ok(model);
+    Ok(model)
 }
 
 /// Checks to see if a set command is for the run configuration model and dispatches the 
@@ -277,23 +288,25 @@ pub fn parse_set_model(command: &str, model: &BabyModel) -> Result<BabyModel, St
 /// # Parameters 
 /// * `command` - The string command stating what is being set. 
 /// * `conf` - The configuration model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
+/// * `int` - The interface to print help messages. 
 /// 
 /// # Returns 
 /// * [Ok(Run)] - If the parsing and setting suceeded. 
 /// * [Err(String)] - If the parsing failed, contains an error message. 
 /// 
-pub fn parse_set_config(command: &str, conf: &Run, int: &impl Interface) -> Result<Run, String> {
+pub fn parse_set_config(command: &str, conf: &Run, symbols: &Symbols, int: &impl Interface) -> Result<Run, String> {
     let command = command.trim();
     let (next_com, _) = command.split_at(command.find(" ").unwrap_or(command.len()));
     let next_com = next_com.trim();
     let model = match next_com {
-        "debug-addrs" => set_debug_address(command.replace("debug-addrs", ""), conf).map_err(|e| e)?,
-        "break-addrs" => set_break_address(command.replace("break-addrs", ""), conf).map_err(|e| e)?,
-        "debug-regs" => set_debug_regs(command.replace("debug-regs", ""), conf).map_err(|e| e)?,
-        "" | "h" | "help" => { int.log_msg(format!("{}", SET_HELP_MSG)); conf.clone() },
+        "debug-addrs" => set_debug_address(command[next_com.len()..].to_owned(), conf, symbols)?,
+        "break-addrs" => set_break_address(command[next_com.len()..].to_owned(), conf, symbols)?,
+        "debug-regs" => set_debug_regs(command[next_com.len()..].to_owned(), conf)?,
+        "" | "h" | "help" => { int.log_msg(SET_HELP_MSG.to_string()); conf.clone() },
         _ => return Err(format!("No recognised set command `{}`.", command))
     };
-    return Ok(model)
+    Ok(model)
 }
 
 /// Parses a set command, tries to dispatch the relevant command. 
@@ -302,6 +315,7 @@ pub fn parse_set_config(command: &str, conf: &Run, int: &impl Interface) -> Resu
 /// * `command` - The string command stating what is being set. 
 /// * `model` - The model to be acted upon. 
 /// * `conf` - The configuration model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
 /// * `int` - The interface to print help messages. 
 /// 
 /// # Returns 
@@ -312,18 +326,19 @@ pub fn parse_set_command(
     command: String, 
     conf: &Run, 
     model: &BabyModel, 
+    symbols: &Symbols,
     int: &impl Interface
 ) -> Result<(BabyModel, Run), String> {
     let command = command.trim();
-    match parse_set_model(command, model) {
+    match parse_set_model(command, model, symbols) {
         Ok(m) => return Ok((m, conf.clone())),
         Err(e) => e
     };
-    match parse_set_config(command, conf, int) {
+    match parse_set_config(command, conf, symbols, int) {
         Ok(m) => return Ok((model.clone(), m.clone())),
         Err(e) => e
     };
-    return Err(format!("No such option as `{}`. ", command));
+    Err(format!("No such option as `{}`. ", command))
 }
 
 /// Parses a set command, tries to dispatch the relevant command, prints 
@@ -336,10 +351,11 @@ pub fn parse_set_command(
 /// * `command` - The string command stating what is being set. 
 /// * `model` - The model to be acted upon. 
 /// * `conf` - The configuration model to be acted upon. 
+/// * `symbols` - The symbol table to look tag names up in. 
 /// * `int` - The interface to print messages. 
 /// 
-pub fn modify(command: String, conf: &Run, model: &BabyModel, int: &impl Interface) -> (BabyModel, Run) {
-    match parse_set_command(command, conf, model, int) {
+pub fn modify(command: String, conf: &Run, model: &BabyModel, symbols: &Symbols, int: &impl Interface) -> (BabyModel, Run) {
+    match parse_set_command(command, conf, model, symbols, int) {
         Ok(v) => v,
         Err(e) => {
             int.log_warn(e);
@@ -1,7 +1,10 @@
 use baby_emulator::core::BabyModel;
-use super::utils::{parse_memory_addresses, parse_registers};
+use super::utils::{resolve_memory_addresses, parse_registers};
 use crate::args::{Registers, Run};
 use crate::run::output::{output_model, output_all_registers, output_all_memory};
+use crate::run::profile::output_profile;
+use crate::run::display::output_display;
+use crate::run::state::RunState;
 use crate::interface::Interface;
 
 
@@ -10,11 +13,13 @@ pub const PRINT_HELP: &str =
 "Possible sub-commands:
 
 reg accumulator/instruction/instructionaddress - Outputs the registers
-mem 0xA - Output a memory location (max 32, can be hex 0xA, decimal 10, octal 0o12, binary 0b1010)
+mem 0xA - Output a memory location (max 32, can be hex 0xA, decimal 10, octal 0o12, binary 0b1010 or a tag name)
 all-model - Output the whole model
 debug-addrs - The memory addresses to print upon debugging 
 break-addrs - The memory addresses to enter debuging upon hitting 
 debug-regs - The registers to print upon debugging
+profile - The execution profile (requires `--profile`)
+display - The store, accumulator & control lines as the Williams tube display
 help - Print this help command";
 
 
@@ -25,16 +30,17 @@ help - Print this help command";
 /// # Parameters 
 /// * `command` - The string command stating what is being printed. 
 /// * `model` - The model to be read. 
+/// * `state` - The runtime state to read pseudo-registers & tag names from. 
 /// * `int` - The interface to print messages. 
 /// 
-pub fn show_registers(regs: String, model: &BabyModel, int: &impl Interface) {
+pub fn show_registers(regs: String, model: &BabyModel, state: &RunState, int: &impl Interface) {
     let regs = regs.trim().to_owned();
     if regs.is_empty() {
-        output_all_registers(model, int);
+        output_all_registers(model, state, int);
         return;
     }
     match parse_registers(regs.as_str()) {
-        Ok(v) => output_model(&v, &vec![], false, model, int),
+        Ok(v) => output_model(&v, &[], false, model, state, int),
         Err(e) => int.log_warn(format!("Invalid register name: {}", e))
     }
 }
@@ -46,16 +52,17 @@ pub fn show_registers(regs: String, model: &BabyModel, int: &impl Interface) {
 /// # Parameters 
 /// * `command` - The string command stating what is being printed. 
 /// * `model` - The model to be read. 
+/// * `state` - The runtime state to read tag names from. 
 /// * `int` - The interface to print messages. 
 /// 
-pub fn show_memory_addresses(addrs: String, model: &BabyModel, int: &impl Interface) {
+pub fn show_memory_addresses(addrs: String, model: &BabyModel, state: &RunState, int: &impl Interface) {
     let addrs = addrs.trim().to_owned();
     if addrs.is_empty() {
-        output_all_memory(model, int);
+        output_all_memory(model, &state.symbols, int);
         return;
     }
-    match parse_memory_addresses(addrs) {
-        Ok(v) => output_model(&vec![], &v, false, model, int),
+    match resolve_memory_addresses(addrs, &state.symbols) {
+        Ok(v) => output_model(&[], &v, false, model, state, int),
         Err(e) => int.log_warn(format!("Invalid memory address: {}", e))
     }
 }
@@ -66,7 +73,7 @@ pub fn show_memory_addresses(addrs: String, model: &BabyModel, int: &impl Interf
 /// * `addrs` - The addresses to be printed. 
 /// * `int` - The interface to print messages. 
 /// 
-pub fn print_addresses(addrs: &Vec<usize>, int: &impl Interface) {
+pub fn print_addresses(addrs: &[usize], int: &impl Interface) {
     let addresses = addrs.iter()
         .map(|v| format!("{:#04x}", v))
         .collect::<Vec<String>>()
@@ -80,7 +87,7 @@ pub fn print_addresses(addrs: &Vec<usize>, int: &impl Interface) {
 /// * `regs` - The registers to be printed. 
 /// * `int` - The interface to print messages. 
 /// 
-pub fn print_registers(regs: &Vec<Registers>, int: &impl Interface) {
+pub fn print_registers(regs: &[Registers], int: &impl Interface) {
     let regs = regs.iter()
         .map(|v| format!("{:?}", v))
         .collect::<Vec<String>>()
@@ -97,20 +104,26 @@ pub fn print_registers(regs: &Vec<Registers>, int: &impl Interface) {
 /// * `command` - The string command stating what is being printed. 
 /// * `model` - The model to be read. 
 /// * `conf` - The configuration model to be read. 
+/// * `state` - The runtime state to be read. 
 /// * `int` - The interface to print messages. 
 /// 
-pub fn print(command: String, conf: &Run, model: &BabyModel, int: &impl Interface) {
+pub fn print(command: String, conf: &Run, model: &BabyModel, state: &RunState, int: &impl Interface) {
     let command = command.trim();
     let (next_com, _) = command.split_at(command.find(" ").unwrap_or(command.len()));
     let next_com = next_com.trim();
     match next_com {
-        "reg" => show_registers(command.replace("reg", ""), model, int),
-        "mem" => show_memory_addresses(command.replace("mem", ""), model, int),
-        "all-model" => output_model(&vec![], &vec![], true, model, int),
+        "reg" => show_registers(command[next_com.len()..].to_owned(), model, state, int),
+        "mem" => show_memory_addresses(command[next_com.len()..].to_owned(), model, state, int),
+        "all-model" => output_model(&[], &[], true, model, state, int),
         "debug-addrs" => print_addresses(&conf.output_addr, int),
         "break-addrs" => print_addresses(&conf.break_addr, int),
         "debug-regs" => print_registers(&conf.output_regs, int),
-        "" | "h" | "help" => int.log_msg(format!("{}", PRINT_HELP)),
+        "display" => output_display(model, int),
+        "profile" => match &state.profile {
+            Some(p) => output_profile(p, int),
+            None => int.log_warn("Profiling is not enabled, run with `--profile`. ".to_string())
+        },
+        "" | "h" | "help" => int.log_msg(PRINT_HELP.to_string()),
         _ => int.log_warn(format!("No recognised print command `{}`. \n{}", command, PRINT_HELP))
     }
 }
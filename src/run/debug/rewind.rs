@@ -0,0 +1,46 @@
+use baby_emulator::core::BabyModel;
+use crate::args::Run;
+use crate::run::state::RunState;
+
+
+/// Steps back a number of instructions parsed from a command string. 
+/// 
+/// Defaults to a single instruction if no number is given, updates the 
+/// clock to match the state rewound to. 
+/// 
+/// # Parameters 
+/// * `command` - The string command containing the number of instructions. 
+/// * `state` - The runtime state holding the history. 
+/// 
+/// # Returns 
+/// * [Ok(BabyModel)] - The model rewound to. 
+/// * [Err(String)] - If the parsing failed or there isn't enough history, contains an error message. 
+/// 
+pub fn step_back(command: String, state: &mut RunState) -> Result<BabyModel, String> {
+    let command = command.trim();
+    let count = if command.is_empty() { 1 }
+        else { command.parse::<usize>().map_err(|_| format!("Invalid number of steps `{}`. ", command))? };
+    let (clock, model) = state.history.back(count)
+        .ok_or(format!("Cannot step back {} instructions, {} previous states are recorded. ", count, state.history.len()))?;
+    state.clock = clock;
+    Ok(model)
+}
+
+/// Rewinds to the last time a breakpoint was hit. 
+/// 
+/// Updates the clock to match the state rewound to. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration containing the breakpoints. 
+/// * `state` - The runtime state holding the history. 
+/// 
+/// # Returns 
+/// * [Ok(BabyModel)] - The model rewound to. 
+/// * [Err(String)] - If no breakpoint was hit in the recorded history, contains an error message. 
+/// 
+pub fn reverse_continue(conf: &Run, state: &mut RunState) -> Result<BabyModel, String> {
+    let (clock, model) = state.history.back_to(&conf.break_addr)
+        .ok_or(format!("No breakpoint hit in the {} previous recorded states. ", state.history.len()))?;
+    state.clock = clock;
+    Ok(model)
+}
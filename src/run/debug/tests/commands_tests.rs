@@ -1,41 +1,60 @@
 use std::path::PathBuf;
 use super::commands;
 use super::commands::HELP;
-use crate::test_utils::TestInterface;
-use crate::args::{Run, ExecuteFrom, Registers};
+use crate::run::state::RunState;
+use crate::test_utils::{TestInterface, default_run};
+use crate::run::snapshot::Snapshot;
+use crate::symbols::Symbols;
 use baby_emulator::core::BabyModel;
 
 
-fn default_run() -> Run {
-    Run {
-        src: PathBuf::from("foo"),
-        exe_from: ExecuteFrom::Bin,
-        og_notation: false,
-        output_model: false,
-        debug_on_err: true,
-        output_addr: vec![5],
-        output_regs: vec![Registers::Accumulator],
-        break_addr: vec![6]
-    }
-}
-
 #[test]
 fn test_match_debug_command_help() {
     let model = BabyModel::new();
     let conf = default_run();
-    let test_int = TestInterface::new_logger_test(format!("{}", HELP).as_str(), "", "");
-    commands::match_debug_command(format!("      "), &conf, &model, &test_int);
-    commands::match_debug_command(format!("   h   "), &conf, &model, &test_int);
-    commands::match_debug_command(format!("   H   "), &conf, &model, &test_int);
-    commands::match_debug_command(format!("   HELP   "), &conf, &model, &test_int); 
-    commands::match_debug_command(format!("   help   "), &conf, &model, &test_int); 
+    let state = RunState::new(0, false);
+    let test_int = TestInterface::new_logger_test(HELP.to_string().as_str(), "", "");
+    commands::match_debug_command("      ".to_string(), &conf, &model, &state, &test_int);
+    commands::match_debug_command("   h   ".to_string(), &conf, &model, &state, &test_int);
+    commands::match_debug_command("   H   ".to_string(), &conf, &model, &state, &test_int);
+    commands::match_debug_command("   HELP   ".to_string(), &conf, &model, &state, &test_int); 
+    commands::match_debug_command("   help   ".to_string(), &conf, &model, &state, &test_int); 
 }
 
 #[test]
 fn test_match_debug_command_err() {
     let model = BabyModel::new();
     let conf = default_run();
+    let state = RunState::new(0, false);
     let test_int = TestInterface::new_logger_test("", "No such command as `foo`, use help for a list of commands. ", "");
-    commands::match_debug_command(format!("   foo   "), &conf, &model, &test_int);
+    commands::match_debug_command("   foo   ".to_string(), &conf, &model, &state, &test_int);
 }
 
+
+#[test]
+fn test_match_debug_command_save() {
+    let model = BabyModel::new();
+    let conf = default_run();
+    let mut state = RunState::new(0, false);
+    state.clock.steps = 3;
+    state.clock.beats = 12;
+    let mut test_int = TestInterface::new_logger_test("Saved snapshot to `Snap.txt`. ", "", "");
+    test_int.should_write_addr = PathBuf::from("Snap.txt");
+    test_int.should_write_data = Snapshot::new(&model, &state.clock, &state.symbols, &conf).serialise().into_bytes();
+    commands::match_debug_command("  save  Snap.txt ".to_string(), &conf, &model, &state, &test_int);
+
+    let test_int = TestInterface::new_logger_test("", "Please specify a file to save the snapshot to. ", "");
+    commands::match_debug_command("  save   ".to_string(), &conf, &model, &state, &test_int);
+}
+
+#[test]
+fn test_match_debug_command_keyword_labels() {
+    let model = BabyModel::new();
+    let conf = default_run();
+    let mut state = RunState::new(0, false);
+    state.symbols = Symbols::parse("0x05 settle\n0x06 memory\n").ok().unwrap();
+    let test_int = TestInterface::new_logger_test("0x06: 0x00000000 (memory)", "", "");
+    let (model, _) = commands::match_debug_command("set mem settle 0x3".to_string(), &conf, &model, &state, &test_int);
+    assert_eq!(model.main_store[5], 3);
+    commands::match_debug_command("print mem memory".to_string(), &conf, &model, &state, &test_int);
+}
@@ -9,3 +9,7 @@ mod modify_tests;
 mod utils_tests;
 #[cfg(test)]
 mod print_tests;
+#[cfg(test)]
+mod rewind_tests;
+#[cfg(test)]
+mod tui_tests;
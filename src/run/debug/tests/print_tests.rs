@@ -1,8 +1,9 @@
-use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
 use baby_emulator::core::{BabyModel, MEMORY_WORDS, instructions::BabyInstruction};
-use crate::test_utils::{TestInterface, TestSucessiveInterface, TestApplyInterface};
-use crate::args::{Run, Registers, ExecuteFrom};
+use crate::run::state::RunState;
+use crate::test_utils::{TestInterface, TestSucessiveInterface, TestApplyInterface, default_run};
+use crate::args::Registers;
+use crate::symbols::Symbols;
 use super::print;
 use super::print::PRINT_HELP;
 
@@ -20,14 +21,15 @@ fn test_show_registers() {
                 BabyInstruction::from_number(model.instruction).get_instr_description()
             ).as_str(),
             format!("{:#010x}", model.instruction_address).as_str(),
+            "0 beats",
         ], 
         vec![], 
         vec![]
     );
-    print::show_registers(format!("   Accumulator   ,   instruction   ,   InstructionAddress   "), &model, &test_int);
+    print::show_registers("   Accumulator   ,   instruction   ,   InstructionAddress   ".to_string(), &model, &RunState::new(0, false), &test_int);
     test_int.log_count = AtomicUsize::new(0);
-    print::show_registers(format!("      "), &model, &test_int);
-    print::show_registers(format!("   Accumulator   ,   foo   "), &model, &test_err_int);
+    print::show_registers("      ".to_string(), &model, &RunState::new(0, false), &test_int);
+    print::show_registers("   Accumulator   ,   foo   ".to_string(), &model, &RunState::new(0, false), &test_err_int);
 }
 
 #[test]
@@ -44,7 +46,7 @@ fn test_show_memory_addresses() {
     }
     let mut test_int = TestSucessiveInterface::new_logger_test(
         vec![
-            format!("{:#04x}: {:#010x}", 0, model.main_store[0 & 0x1F]).as_str(),
+            format!("{:#04x}: {:#010x}", 0, model.main_store[0]).as_str(),
             format!("{:#04x}: {:#010x}", 1, model.main_store[1 & 0x1F]).as_str(),
             format!("{:#04x}: {:#010x}", 2, model.main_store[2 & 0x1F]).as_str(),
             format!("{:#04x}: {:#010x}", 10, model.main_store[10 & 0x1F]).as_str(),
@@ -52,11 +54,25 @@ fn test_show_memory_addresses() {
         vec![], 
         vec![]
     );
-    print::show_memory_addresses(format!("   0   ,   0b1   ,   0o2   ,   0xA   "), &model, &test_int);
+    print::show_memory_addresses("   0   ,   0b1   ,   0o2   ,   0xA   ".to_string(), &model, &RunState::new(0, false), &test_int);
     test_int.log_count = AtomicUsize::new(0);
     test_int.should_log = should_log;
-    print::show_memory_addresses(format!("      "), &model, &test_int);
-    print::show_memory_addresses(format!("   0   ,   0xY   "), &model, &apply_int);
+    print::show_memory_addresses("      ".to_string(), &model, &RunState::new(0, false), &test_int);
+    print::show_memory_addresses("   0   ,   0xY   ".to_string(), &model, &RunState::new(0, false), &apply_int);
+}
+
+#[test]
+fn test_print_with_labels() {
+    let model = BabyModel::new();
+    let conf = default_run();
+    let mut state = RunState::new(0, false);
+    state.symbols = Symbols::parse("0x05 memory\n0x06 register\n0x07 offset\n0x08 reset\n").ok().unwrap();
+    let test_int = TestSucessiveInterface::new_logger_test(
+        vec!["0x05: 0x00000000 (memory)", "0x06: 0x00000000 (register)", "0x07: 0x00000000 (offset)", "0x08: 0x00000000 (reset)"],
+        vec![],
+        vec![]
+    );
+    print::print(" mem memory, register, offset, reset ".to_string(), &conf, &model, &state, &test_int);
 }
 
 #[test]
@@ -81,40 +97,39 @@ fn test_print_registers() {
     print::print_registers(&regs, &test_int);
 }
 
-fn default_run() -> Run {
-    Run {
-        src: PathBuf::from("foo"),
-        exe_from: ExecuteFrom::Bin,
-        og_notation: false,
-        output_model: false,
-        debug_on_err: true,
-        output_addr: vec![5],
-        output_regs: vec![Registers::Accumulator],
-        break_addr: vec![6]
-    }
-}
 
 #[test]
 fn test_print_help() {
     let conf = default_run();
+    let state = RunState::new(0, false);
     let model = BabyModel::new();
     let test_int = TestInterface::new_logger_test(
-        format!("{}", PRINT_HELP).as_str(), 
+        PRINT_HELP.to_string().as_str(), 
         "", 
         ""
     );
-    print::print(format!("   help   "), &conf, &model, &test_int);
-    print::print(format!("   h   "), &conf, &model, &test_int);
+    print::print("   help   ".to_string(), &conf, &model, &state, &test_int);
+    print::print("   h   ".to_string(), &conf, &model, &state, &test_int);
 }
 
 #[test]
 fn test_print_err() {
     let conf = default_run();
+    let state = RunState::new(0, false);
     let model = BabyModel::new();
     let test_int = TestApplyInterface::new_logger_test(
         |_| {}, 
         |s| assert!(s.starts_with("No recognised print command")), 
         |_| {}
     );
-    print::print(format!("   foo   "), &conf, &model, &test_int);
+    print::print("   foo   ".to_string(), &conf, &model, &state, &test_int);
+}
+
+#[test]
+fn test_print_profile_disabled() {
+    let conf = default_run();
+    let state = RunState::new(0, false);
+    let model = BabyModel::new();
+    let test_int = TestInterface::new_logger_test("", "Profiling is not enabled, run with `--profile`. ", "");
+    print::print("   profile   ".to_string(), &conf, &model, &state, &test_int);
 }
@@ -0,0 +1,62 @@
+use super::rewind;
+use crate::args::Run;
+use crate::test_utils::default_run;
+use crate::run::state::RunState;
+use crate::run::clock::Clock;
+use baby_emulator::core::BabyModel;
+
+
+fn default_state() -> RunState {
+    let mut state = RunState::new(10, false);
+    for i in 0..5 {
+        let mut model = BabyModel::new();
+        model.instruction_address = i as u16;
+        state.history.push(Clock { steps: i, beats: i * 4 }, &model);
+    }
+    state.clock = Clock { steps: 5, beats: 20 };
+    state
+}
+
+#[test]
+fn test_step_back() {
+    let mut state = default_state();
+    match rewind::step_back("   ".to_string(), &mut state) {
+        Ok(m) => assert_eq!(m.instruction_address, 4),
+        Err(_) => panic!("Stepping back failed. ")
+    }
+    assert_eq!(state.clock, Clock { steps: 4, beats: 16 });
+    match rewind::step_back("  3  ".to_string(), &mut state) {
+        Ok(m) => assert_eq!(m.instruction_address, 1),
+        Err(_) => panic!("Stepping back multiple instructions failed. ")
+    }
+    assert_eq!(state.clock.steps, 1);
+}
+
+#[test]
+fn test_step_back_err() {
+    let mut state = default_state();
+    match rewind::step_back(" foo ".to_string(), &mut state) {
+        Err(e) => assert_eq!(e, format!("Invalid number of steps `foo`. ")),
+        Ok(_) => panic!("Invalid step back command succeeded. ")
+    }
+    match rewind::step_back(" 6 ".to_string(), &mut state) {
+        Err(e) => assert_eq!(e, format!("Cannot step back 6 instructions, 5 previous states are recorded. ")),
+        Ok(_) => panic!("Stepping back past the history succeeded. ")
+    }
+    assert_eq!(state.clock.steps, 5);
+}
+
+#[test]
+fn test_reverse_continue() {
+    let conf = Run { break_addr: vec![2], ..default_run() };
+    let mut state = default_state();
+    match rewind::reverse_continue(&conf, &mut state) {
+        Ok(m) => assert_eq!(m.instruction_address, 2),
+        Err(_) => panic!("Reverse continue failed. ")
+    }
+    assert_eq!(state.clock.steps, 2);
+    match rewind::reverse_continue(&conf, &mut state) {
+        Err(e) => assert_eq!(e, format!("No breakpoint hit in the 2 previous recorded states. ")),
+        Ok(_) => panic!("Reverse continue without a breakpoint succeeded. ")
+    }
+}
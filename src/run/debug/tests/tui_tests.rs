@@ -0,0 +1,91 @@
+use super::tui;
+use super::DebugResult;
+use crate::run::state::RunState;
+use crate::test_utils::{TestInterface, TestSucessiveInterface, default_run};
+use crate::interface::Interface;
+use crate::args::Run;
+use baby_emulator::core::BabyModel;
+
+
+fn tui_run() -> Run {
+    Run { break_addr: vec![2, 5], tui: true, ..default_run() }
+}
+
+#[test]
+fn test_format_instruction() {
+    assert_eq!(tui::format_instruction(0x4005), "LDN 0x05");
+    assert_eq!(tui::format_instruction(0xE000), "STP");
+}
+
+#[test]
+fn test_store_pane() {
+    let conf = tui_run();
+    let mut model = BabyModel::new();
+    model.instruction_address = 1;
+    model.main_store[2] = 0x4005;
+    let pane = tui::store_pane(&model, &conf);
+    assert_eq!(pane.len(), 33);
+    assert_eq!(pane[1], format!("{:<28}", "   0x00: 0x00000000"));
+    assert!(pane[2].contains(">  0x01: 0x00000000"));
+    assert_eq!(pane[3], format!("{:<28}", " * 0x02: 0x00004005"));
+}
+
+#[test]
+fn test_disassembly_pane() {
+    let mut model = BabyModel::new();
+    model.main_store[31] = 0x4005;
+    let pane = tui::disassembly_pane(&model);
+    assert_eq!(pane.len(), 10);
+    assert_eq!(pane[1], "  0x1c: JMP 0x00");
+    assert_eq!(pane[4], "  0x1f: LDN 0x05");
+    assert_eq!(pane[5], "> 0x00: JMP 0x00");
+    assert_eq!(pane[9], "  0x04: JMP 0x00");
+}
+
+#[test]
+fn test_breakpoint_pane() {
+    let mut conf = tui_run();
+    assert_eq!(tui::breakpoint_pane(&conf), vec!["Breakpoints", "0x02, 0x05"]);
+    conf.break_addr = vec![];
+    assert_eq!(tui::breakpoint_pane(&conf), vec!["Breakpoints", "none"]);
+}
+
+#[test]
+fn test_render_frame() {
+    let conf = tui_run();
+    let state = RunState::new(0, false);
+    let frame = tui::render_frame(&BabyModel::new(), &conf, &state);
+    assert_eq!(frame.len(), 33);
+    assert_eq!(frame[0], format!("{:<28} | Registers", "Store"));
+    assert_eq!(frame[4], format!("{:<28} | Steps 0", "   0x03: 0x00000000"));
+    assert_eq!(frame[5], format!("{:<28} | Beats 0", "   0x04: 0x00000000"));
+    assert_eq!(frame[20], format!("{:<28} |", "   0x13: 0x00000000"));
+}
+
+#[test]
+fn test_capture_interface() {
+    let int = TestInterface::new_logger_test("", "", "");
+    let capture = tui::CaptureInterface::new(&int);
+    capture.log_msg("foo\nbar".to_string());
+    capture.log_inline("baz".to_string());
+    assert_eq!(capture.take(), vec!["foo", "bar", "baz"]);
+    assert!(capture.take().is_empty());
+}
+
+#[test]
+fn test_check_tui_session() {
+    let conf = tui_run();
+    let model = BabyModel::new();
+    let mut state = RunState::new(0, false);
+    let frame = tui::render_frame(&model, &conf, &state);
+    let mut should_log: Vec<&str> = frame.iter().map(|v| v.as_str()).collect();
+    should_log.push("");
+    should_log.extend(frame.iter().map(|v| v.as_str()));
+    should_log.extend(["", "0x01: 0x00000000"]);
+    let mut test_int = TestSucessiveInterface::new_logger_test(should_log, vec![], vec![]);
+    test_int.line_return = vec![format!("print mem 0x1"), format!("n")];
+    match tui::check_tui_session(&model, &conf, &mut state, &test_int) {
+        DebugResult::SingleStep(_, _) => (),
+        _ => panic!("Stepping from the TUI didn't single step. ")
+    }
+}
@@ -1,5 +1,6 @@
 use crate::args::Registers;
 use super::utils;
+use crate::symbols::Symbols;
 use baby_emulator::core::MEMORY_WORDS;
 
 
@@ -8,7 +9,7 @@ fn test_parse_register() {
     assert_eq!(utils::parse_register("   accumulator   "), Ok(Registers::Accumulator));
     assert_eq!(utils::parse_register("   instruction   "), Ok(Registers::Instruction));
     assert_eq!(utils::parse_register("   InstructionAddress   "), Ok(Registers::InstructionAddress));
-    assert_eq!(utils::parse_register("   foo   "), Err(format!("foo")));
+    assert_eq!(utils::parse_register("   foo   "), Err("foo".to_string()));
 }
 
 #[test]
@@ -19,7 +20,7 @@ fn test_parse_registers() {
     );
     assert_eq!(
         utils::parse_registers("   accumulator   ,  instruction  ,  InstructionAddress,  foo   "),
-        Err(format!("foo"))
+        Err("foo".to_string())
     );
 }
 
@@ -30,7 +31,7 @@ fn test_parse_memory_address() {
     assert_eq!(utils::parse_memory_address("   0b1010   "), Ok(10));
     assert_eq!(utils::parse_memory_address("   0d10   "), Ok(10));
     assert_eq!(utils::parse_memory_address("   10   "), Ok(10));
-    assert_eq!(utils::parse_memory_address("   foo   "), Err(format!("foo")));
+    assert_eq!(utils::parse_memory_address("   foo   "), Err("foo".to_string()));
     match utils::parse_memory_address("   33   ") {
         Err(e) => assert_eq!(e, format!("Value passed output memory address: 33, Baby memory address space is {MEMORY_WORDS}. ")),
         Ok(_) => panic!("Incorect memory address sucessfully parsed. ")
@@ -44,7 +45,7 @@ fn test_parse_memory_value() {
     assert_eq!(utils::parse_memory_value("   0b1010   "), Ok(10));
     assert_eq!(utils::parse_memory_value("   0d10   "), Ok(10));
     assert_eq!(utils::parse_memory_value("   10   "), Ok(10));
-    assert_eq!(utils::parse_memory_value("   foo   "), Err(format!("foo")));
+    assert_eq!(utils::parse_memory_value("   foo   "), Err("foo".to_string()));
 }
 
 #[test]
@@ -54,21 +55,33 @@ fn test_parse_instruction() {
     assert_eq!(utils::parse_instruction("   0b1010   "), Ok(10));
     assert_eq!(utils::parse_instruction("   0d10   "), Ok(10));
     assert_eq!(utils::parse_instruction("   10   "), Ok(10));
-    assert_eq!(utils::parse_instruction("   foo   "), Err(format!("foo")));
+    assert_eq!(utils::parse_instruction("   foo   "), Err("foo".to_string()));
 }
 
 #[test]
 fn test_parse_memory_addresses() {
     assert_eq!(
-        utils::parse_memory_addresses(format!("   0xa   ,  0O12  ,  0b1010   ,   0d10   ,   10   ")),
+        utils::parse_memory_addresses("   0xa   ,  0O12  ,  0b1010   ,   0d10   ,   10   ".to_string()),
         Ok(vec![10, 10, 10, 10, 10])
     );
     assert_eq!(
-        utils::parse_memory_addresses(format!("   10   ,   foo   ")),
-        Err(format!("Invalid value passed as output memory address `foo`. "))
+        utils::parse_memory_addresses("   10   ,   foo   ".to_string()),
+        Err("Invalid value passed as output memory address `foo`. ".to_string())
     );
-    match utils::parse_memory_addresses(format!("   10   ,   33   ")) {
+    match utils::parse_memory_addresses("   10   ,   33   ".to_string()) {
         Err(e) => assert!(e.starts_with("Invalid value passed as output memory address")),
         Ok(_) => panic!("Invalid arg to parse memory addresses succeeded. ")
     }
 }
+
+#[test]
+fn test_resolve_memory_addresses() {
+    let symbols = Symbols::parse("0x05 loop\n0x1e counter\n").ok().unwrap();
+    assert_eq!(utils::resolve_memory_address("   LOOP   ", &symbols), Ok(5));
+    assert_eq!(utils::resolve_memory_address("   0x6   ", &symbols), Ok(6));
+    assert_eq!(utils::resolve_memory_address("   foo   ", &Symbols::new()), Err("foo".to_string()));
+    assert_eq!(
+        utils::resolve_memory_addresses("  loop  ,  counter  ,  2  ".to_string(), &symbols),
+        Ok(vec![5, 30, 2])
+    );
+}
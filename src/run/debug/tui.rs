@@ -0,0 +1,298 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use baby_emulator::core::{BabyModel, MEMORY_WORDS, instructions::BabyInstruction};
+use colored::Colorize;
+use crate::args::Run;
+use crate::interface::Interface;
+use crate::notation::describe_instruction;
+use crate::run::state::RunState;
+use super::{debug_command, CommandResult, DebugResult};
+
+
+/// Clears the terminal and moves the cursor to the top left. 
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Switches the terminal to its alternate screen, leaving the user's scrollback untouched. 
+pub const ENTER_ALT_SCREEN: &str = "\x1b[?1049h";
+
+/// Switches the terminal back from its alternate screen. 
+pub const LEAVE_ALT_SCREEN: &str = "\x1b[?1049l";
+
+/// The width of the store pane, the other panes are drawn to the right of it. 
+pub const STORE_WIDTH: usize = 28;
+
+/// The number of store lines shown either side of the current instruction in the disassembly pane. 
+pub const DISASSEMBLY_CONTEXT: usize = 4;
+
+/// The shortest time between redraws whilst the program is running. 
+pub const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Wraps an interface, holding onto any messages so they can be drawn 
+/// in the output pane rather than scrolling the screen. 
+pub struct CaptureInterface<'a, T: Interface> {
+    /// The interface being wrapped. 
+    inner: &'a T,
+    /// The messages logged since the last take. 
+    lines: RefCell<Vec<String>>,
+}
+
+impl<'a, T: Interface> CaptureInterface<'a, T> {
+    /// Creates a new capturing interface. 
+    /// 
+    /// # Parameters 
+    /// * `inner` - The interface to be wrapped. 
+    /// 
+    pub fn new(inner: &'a T) -> CaptureInterface<'a, T> {
+        CaptureInterface { inner, lines: RefCell::new(vec![]) }
+    }
+
+    /// Takes all the messages logged since the last take. 
+    pub fn take(&self) -> Vec<String> {
+        self.lines.take()
+    }
+
+    /// Holds onto a message, splitting it into lines. 
+    fn push(&self, msg: String) {
+        self.lines.borrow_mut().extend(msg.lines().map(|v| v.to_owned()));
+    }
+}
+
+impl<'a, T: Interface> Interface for CaptureInterface<'a, T> {
+    fn log_msg(&self, msg: String) {
+        self.push(msg);
+    }
+    fn log_inline(&self, msg: String) {
+        self.push(msg);
+    }
+    fn log_warn(&self, msg: String) {
+        self.push(format!("{}", msg.as_str().yellow()));
+    }
+    fn log_error(&self, msg: String) {
+        self.push(format!("{}", msg.as_str().red()));
+    }
+    fn get_line(&self) -> String {
+        self.inner.get_line()
+    }
+    fn read_fs_string(&self, path: &Path) -> Result<String, ()> {
+        self.inner.read_fs_string(path)
+    }
+    fn read_fs_bytes(&self, path: &Path) -> Result<Vec<u8>, ()> {
+        self.inner.read_fs_bytes(path)
+    }
+    fn write_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
+        self.inner.write_fs_bytes(data, out)
+    }
+    fn append_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
+        self.inner.append_fs_bytes(data, out)
+    }
+    fn sleep(&self, duration: Duration) {
+        self.inner.sleep(duration)
+    }
+    fn watch_interrupts(&self) {
+        self.inner.watch_interrupts()
+    }
+    fn interrupted(&self) -> bool {
+        self.inner.interrupted()
+    }
+}
+
+/// Formats a word as an instruction in modern notation. 
+/// 
+/// # Parameters 
+/// * `word` - The word to be formatted. 
+/// 
+pub fn format_instruction(word: i32) -> String {
+    describe_instruction(&BabyInstruction::from_number(word as u16))
+}
+
+/// Renders the store pane, every store line marked with `>` if it's the 
+/// current instruction and `*` if it's a breakpoint, padded to [STORE_WIDTH]. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be rendered. 
+/// * `conf` - The configuration containing the breakpoints. 
+/// 
+pub fn store_pane(model: &BabyModel, conf: &Run) -> Vec<String> {
+    let current = model.instruction_address as usize & 0x1F;
+    let mut res = vec![format!("{:<width$}", "Store", width = STORE_WIDTH)];
+    for i in 0..MEMORY_WORDS {
+        let line = format!(
+            "{}{} {:#04x}: {:#010x}",
+            if i == current { ">" } else { " " },
+            if conf.break_addr.contains(&i) { "*" } else { " " },
+            i, model.main_store[i]
+        );
+        let line = format!("{:<width$}", line, width = STORE_WIDTH);
+        res.push(if i == current { format!("{}", line.as_str().reversed()) } else { line });
+    }
+    res
+}
+
+/// Renders the registers pane, including the step & beat counts. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be rendered. 
+/// * `state` - The runtime state holding the clock. 
+/// 
+pub fn register_pane(model: &BabyModel, state: &RunState) -> Vec<String> {
+    vec![
+        format!("Registers"),
+        format!("A     {:#010x} ({})", model.accumulator, model.accumulator),
+        format!("CI    {:#06x}", model.instruction_address),
+        format!("PI    {:#06x} ({})", model.instruction, format_instruction(model.instruction as i32)),
+        format!("Steps {}", state.clock.steps),
+        format!("Beats {}", state.clock.beats),
+    ]
+}
+
+/// Renders the disassembly pane, the store lines around the current instruction 
+/// disassembled, wrapping around the store. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be rendered. 
+/// 
+pub fn disassembly_pane(model: &BabyModel) -> Vec<String> {
+    let current = model.instruction_address as usize & 0x1F;
+    let mut res = vec![format!("Disassembly")];
+    for offset in 0..(DISASSEMBLY_CONTEXT * 2 + 1) {
+        let addr = (current + MEMORY_WORDS + offset - DISASSEMBLY_CONTEXT) % MEMORY_WORDS;
+        res.push(format!(
+            "{} {:#04x}: {}",
+            if addr == current { ">" } else { " " },
+            addr,
+            format_instruction(model.main_store[addr])
+        ));
+    }
+    res
+}
+
+/// Renders the breakpoints pane. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration containing the breakpoints. 
+/// 
+pub fn breakpoint_pane(conf: &Run) -> Vec<String> {
+    let addrs = if conf.break_addr.is_empty() { "none".to_string() }
+        else {
+            conf.break_addr.iter()
+                .map(|v| format!("{:#04x}", v))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+    vec![format!("Breakpoints"), addrs]
+}
+
+/// Renders the whole screen, the store pane on the left with the registers, 
+/// disassembly and breakpoint panes stacked on the right. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be rendered. 
+/// * `conf` - The configuration to be rendered. 
+/// * `state` - The runtime state to be rendered. 
+/// 
+pub fn render_frame(model: &BabyModel, conf: &Run, state: &RunState) -> Vec<String> {
+    let left = store_pane(model, conf);
+    let right = [register_pane(model, state), disassembly_pane(model), breakpoint_pane(conf)]
+        .join(&String::new());
+    (0..left.len().max(right.len()))
+        .map(|i| {
+            let l = left.get(i).cloned().unwrap_or(" ".repeat(STORE_WIDTH));
+            let r = right.get(i).cloned().unwrap_or(String::new());
+            format!("{} | {}", l, r).trim_end().to_owned()
+        })
+        .collect()
+}
+
+/// Draws the whole screen with the output of the last command under it. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be drawn. 
+/// * `conf` - The configuration to be drawn. 
+/// * `state` - The runtime state to be drawn. 
+/// * `output` - The output of the last command. 
+/// * `int` - The interface to draw to. 
+/// 
+pub fn draw(model: &BabyModel, conf: &Run, state: &RunState, output: &[String], int: &impl Interface) {
+    int.log_inline(CLEAR_SCREEN.to_string());
+    render_frame(model, conf, state).into_iter().for_each(|v| int.log_msg(v));
+    int.log_msg(String::new());
+    output.iter().for_each(|v| int.log_msg(v.clone()));
+}
+
+/// Runs a full-screen debug session, redrawing the screen after every command. 
+/// 
+/// Accepts the same commands as the line debugger, their output is shown 
+/// under the panes until the next command. Any interrupt caught whilst at 
+/// the prompt is dropped so it doesn't break straight back in on continuing. 
+/// 
+/// # Parameters 
+/// * `model` - The simulation model to run aganst. 
+/// * `conf` - The configuration model to run against. 
+/// * `state` - The runtime state, rewound by stepping back. 
+/// * `int` - The interface used to i/o by the debug session. 
+/// 
+pub fn check_tui_session(
+    model: &BabyModel,
+    conf: &Run,
+    state: &mut RunState,
+    int: &impl Interface
+) -> DebugResult {
+    let (mut model, mut conf) = (model.clone(), conf.clone());
+    let capture = CaptureInterface::new(int);
+    let mut output = vec![];
+    loop {
+        draw(&model, &conf, state, &output, int);
+        int.log_inline("(ssemu-debug) ".to_string());
+
+        (model, conf) = match debug_command(int.get_line(), model, conf, state, &capture) {
+            CommandResult::Exit(res) => { int.interrupted(); return res },
+            CommandResult::Stay(m, c) => (m, c),
+        };
+        output = capture.take();
+    }
+}
+
+/// Redraws the screen whilst a program is running, at most once every [REFRESH_INTERVAL]. 
+pub struct LiveView {
+    /// The last time the screen was drawn. 
+    last: Option<Instant>,
+}
+
+impl LiveView {
+    /// Creates a new view that hasn't been drawn, switching to the alternate screen 
+    /// & catching interrupts so the user can break into the running program. 
+    /// 
+    /// # Parameters 
+    /// * `int` - The interface to draw to. 
+    /// 
+    pub fn open(int: &impl Interface) -> LiveView {
+        int.log_inline(ENTER_ALT_SCREEN.to_string());
+        int.watch_interrupts();
+        LiveView { last: None }
+    }
+
+    /// Switches back from the alternate screen, restoring the user's scrollback. 
+    /// 
+    /// # Parameters 
+    /// * `int` - The interface drawn to. 
+    /// 
+    pub fn close(&self, int: &impl Interface) {
+        int.log_inline(LEAVE_ALT_SCREEN.to_string());
+    }
+
+    /// Redraws the screen if it hasn't been drawn recently. 
+    /// 
+    /// # Parameters 
+    /// * `model` - The model to be drawn. 
+    /// * `conf` - The configuration to be drawn. 
+    /// * `state` - The runtime state to be drawn. 
+    /// * `int` - The interface to draw to. 
+    /// 
+    pub fn update(&mut self, model: &BabyModel, conf: &Run, state: &RunState, int: &impl Interface) {
+        let now = Instant::now();
+        if self.last.is_some_and(|v| now < v + REFRESH_INTERVAL) { return; }
+        self.last = Some(now);
+        draw(model, conf, state, &["Running... (Ctrl-C to break)".to_string()], int);
+    }
+}
@@ -1,10 +1,11 @@
 use baby_emulator::core::MEMORY_WORDS;
 use crate::args::Registers;
+use crate::symbols::Symbols;
 
 
 /// Parses a register name from a given string. 
 /// 
-/// Value can be `accumulator`, `instruction`, `instructionaddress` (case insensitive). 
+/// Value can be `accumulator`, `instruction`, `instructionaddress`, `beats` (case insensitive). 
 /// 
 /// # Parameters 
 /// * `value` - The string containing a register name. 
@@ -19,13 +20,14 @@ pub fn parse_register(input: &str) -> Result<Registers, String> {
         "accumulator" => Ok(Registers::Accumulator),
         "instruction" => Ok(Registers::Instruction),
         "instructionaddress" => Ok(Registers::InstructionAddress),
+        "beats" => Ok(Registers::Beats),
         _ => Err(input.to_string())
     }
 }
 
 /// Parses register names from a given string. 
 /// 
-/// Values can be `accumulator`, `instruction`, `instructionaddress` (case insensitive),
+/// Values can be `accumulator`, `instruction`, `instructionaddress`, `beats` (case insensitive),
 /// and are commo separated. 
 /// 
 /// Uses [parse_register] internally.
@@ -40,7 +42,7 @@ pub fn parse_registers(input: &str) -> Result<Vec<Registers>, String> {
     let mut res = vec![];
     let regs: Vec<&str> = input.split(",").collect();
     for reg in regs {
-        res.push(parse_register(reg).map_err(|e| e)?);
+        res.push(parse_register(reg)?);
     }
     Ok(res)
 }
@@ -62,7 +64,7 @@ pub fn parse_memory_address(value: &str) -> Result<usize, String> {
         v if v.starts_with("0x") => usize::from_str_radix(&v.replace("0x", ""), 16),
         v if v.starts_with("0o") => usize::from_str_radix(&v.replace("0o", ""), 8),
         v if v.starts_with("0b") => usize::from_str_radix(&v.replace("0b", ""), 2),
-        v => usize::from_str_radix(&v.replace("0d", ""), 10),
+        v => v.replace("0d", "").parse::<usize>(),
     }.map_err(|_| value)?;
     if parse_res > MEMORY_WORDS {
         return Err(format!("Value passed output memory address: {parse_res}, Baby memory address space is {MEMORY_WORDS}. "));
@@ -70,6 +72,23 @@ pub fn parse_memory_address(value: &str) -> Result<usize, String> {
     Ok(parse_res)
 }
 
+/// Parses a memory address from a given string, either a tag name from the 
+/// symbol table or a value accepted by [parse_memory_address]. 
+/// 
+/// # Parameters 
+/// * `value` - The string containing a tag name or memory address value. 
+/// * `symbols` - The symbol table to look tag names up in. 
+/// 
+/// # Returns 
+/// * [Ok(usize)] - The memory address value. 
+/// * [Err(String)] - A formated error message if the value is incorrect. 
+pub fn resolve_memory_address(value: &str, symbols: &Symbols) -> Result<usize, String> {
+    match symbols.address_of(value) {
+        Some(v) => Ok(v),
+        None => parse_memory_address(value),
+    }
+}
+
 /// Parses an memory value from a given string. 
 /// 
 /// Value can be hex/decimal/octal/binary (prefixed with `0x`, `0d`, `0o`, or `0b`). 
@@ -87,7 +106,7 @@ pub fn parse_memory_value(value: &str) -> Result<i32, String> {
         v if v.starts_with("0x") => i32::from_str_radix(&v.replace("0x", ""), 16),
         v if v.starts_with("0o") => i32::from_str_radix(&v.replace("0o", ""), 8),
         v if v.starts_with("0b") => i32::from_str_radix(&v.replace("0b", ""), 2),
-        v => i32::from_str_radix(&v.replace("0d", ""), 10),
+        v => v.replace("0d", "").parse::<i32>(),
     }.map_err(|_| value)?;
     Ok(parse_res)
 }
@@ -110,7 +129,7 @@ pub fn parse_instruction(value: &str) -> Result<u16, String> {
         v if v.starts_with("0x") => u16::from_str_radix(&v.replace("0x", ""), 16),
         v if v.starts_with("0o") => u16::from_str_radix(&v.replace("0o", ""), 8),
         v if v.starts_with("0b") => u16::from_str_radix(&v.replace("0b", ""), 2),
-        v => u16::from_str_radix(&v.replace("0d", ""), 10),
+        v => v.replace("0d", "").parse::<u16>(),
     }.map_err(|_| value)?;
     Ok(parse_res)
 }
@@ -128,10 +147,27 @@ pub fn parse_instruction(value: &str) -> Result<u16, String> {
 /// * [Err(String)] - A formated error message if a value is incorrect. 
 /// 
 pub fn parse_memory_addresses(addresses: String) -> Result<Vec<usize>, String> {
+    resolve_memory_addresses(addresses, &Symbols::new())
+}
+
+/// Parses a list of memory address location values or tag names from a given string. 
+/// 
+/// Parses from a list of comma separated values. 
+/// See [resolve_memory_address] for a list of possible value formats. 
+/// 
+/// # Parameters 
+/// * `addresses` - The string containing a list of memory locations. 
+/// * `symbols` - The symbol table to look tag names up in. 
+/// 
+/// # Returns 
+/// * [Ok(Vec<usize>)] - A vector of memory locations. 
+/// * [Err(String)] - A formated error message if a value is incorrect. 
+/// 
+pub fn resolve_memory_addresses(addresses: String, symbols: &Symbols) -> Result<Vec<usize>, String> {
     let addr_values = addresses.trim().split(",");
     let mut addresses: Vec<usize> = vec![];
     for addr in addr_values {
-        let res = parse_memory_address(addr)
+        let res = resolve_memory_address(addr, symbols)
             .map_err(|e| format!("Invalid value passed as output memory address `{e}`. "))?;
         addresses.push(res);
     }
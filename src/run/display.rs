@@ -0,0 +1,54 @@
+use baby_emulator::core::{BabyModel, MEMORY_WORDS};
+use crate::interface::Interface;
+
+
+/// The number of bits shown on each line of the display. 
+pub const LINE_BITS: usize = 32;
+
+/// The character shown for a 0 bit, a dot on the original tube. 
+pub const DOT: char = '.';
+
+/// The character shown for a 1 bit, a dash on the original tube. 
+pub const DASH: char = '-';
+
+/// Renders a word as a line of dots & dashes, least significant bit first 
+/// as it was shown on the original machine. 
+/// 
+/// # Parameters 
+/// * `word` - The word to be rendered. 
+/// 
+pub fn render_word(word: i32) -> String {
+    (0..LINE_BITS)
+        .map(|i| if (word >> i) & 1 == 1 { DASH } else { DOT })
+        .collect()
+}
+
+/// Renders the store, accumulator & control lines of a model as the 
+/// Williams tube display. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be rendered. 
+/// 
+/// # Returns 
+/// The lines of the display, each labeled by line number or register. 
+/// 
+pub fn render_display(model: &BabyModel) -> Vec<String> {
+    let mut res: Vec<String> = (0..MEMORY_WORDS)
+        .map(|i| format!("{:>2} {}", i, render_word(model.main_store[i])))
+        .collect();
+    res.push(String::new());
+    res.push(format!("{:<2} {}", "A", render_word(model.accumulator)));
+    res.push(format!("{:<2} {}", "CI", render_word(model.instruction_address as i32)));
+    res.push(format!("{:<2} {}", "PI", render_word(model.instruction as i32)));
+    res
+}
+
+/// Outputs the Williams tube display of a model to an interface. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be rendered. 
+/// * `int` - The interface to output to. 
+/// 
+pub fn output_display(model: &BabyModel, int: &impl Interface) {
+    render_display(model).into_iter().for_each(|v| int.log_msg(v));
+}
@@ -1,11 +1,15 @@
 use std::path::PathBuf;
-use baby_emulator::assembler::errors::AssemblyError;
 use baby_emulator::core::MEMORY_WORDS;
+use baby_emulator::core::errors::{BabyErrors, BabyError};
+use crate::errors::{EXIT_FILE_ERROR, EXIT_ASSEMBLER_ERROR, EXIT_INVALID_IMAGE, EXIT_EMULATION_FAULT, EXIT_STEPS_EXCEEDED, EXIT_NO_PROGRESS, EXIT_INTERRUPTED};
 
 
 /// Defines common behaviour for all errors thrown at runtime. 
 pub trait RunError {
+    /// Gets a helper string describing an error. 
     fn describe(&self) -> String;
+    /// Gets the process exit code to report the error with. 
+    fn exit_code(&self) -> i32;
 }
 
 /// Possible errors thrown during reading the program source. 
@@ -14,19 +18,57 @@ pub enum SrcFileErrors {
     CouldntOpenFile(PathBuf),
     /// A binary file was of the wrong length. 
     BinFileWrongLen(usize),
-    /// Failed to assemble a source file. 
-    AssembleError(AssemblyError)
+    /// A binary file wasn't a whole number of words (bytes). 
+    BinFileNotWords(usize),
+    /// A binary image doesn't fit in the store (load address, words). 
+    ImageOverflow(usize, usize),
+    /// Failed to assemble a source file, with every error found rendered. 
+    AssembleError(String),
+    /// A snapshot file was invalid. 
+    InvalidSnapshot(String),
+    /// An image file was invalid. 
+    InvalidImage(String),
+    /// A `.snp` file was invalid. 
+    InvalidSnp(String),
+    /// A symbol file was invalid. 
+    InvalidSymbols(PathBuf, String),
 }
 
 impl RunError for SrcFileErrors {
     fn describe(&self) -> String {
         match self {
             SrcFileErrors::CouldntOpenFile(s) => 
-                format!("Could not open source asm/binary file: `{}`.", s.to_string_lossy().to_string()),
+                format!("Could not open source asm/binary file: `{}`.", s.to_string_lossy()),
             SrcFileErrors::BinFileWrongLen(s) => 
                 format!("The baby memory is {} words long, specified file is `{}` words long. ", MEMORY_WORDS, s/4),
+            SrcFileErrors::BinFileNotWords(s) => 
+                format!("Binary files are made of 4 byte words, specified file is `{}` bytes long. ", s),
+            SrcFileErrors::ImageOverflow(a, s) => 
+                format!("An image of `{}` words loaded at address {:#04x} doesn't fit in the baby memory of {} words. ", s, a, MEMORY_WORDS),
             SrcFileErrors::AssembleError(s) => 
-                format!("Error assembling source asm file. \n{}", s.describe(true)),
+                format!("Error assembling source asm file. \n{}", s),
+            SrcFileErrors::InvalidSnapshot(s) => 
+                format!("Invalid snapshot file. \n{}", s),
+            SrcFileErrors::InvalidImage(s) => 
+                format!("Invalid image file. \n{}", s),
+            SrcFileErrors::InvalidSnp(s) => 
+                format!("Invalid snp file. \n{}", s),
+            SrcFileErrors::InvalidSymbols(p, s) => 
+                format!("Invalid symbol file `{}`. \n{}", p.to_string_lossy(), s),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            SrcFileErrors::CouldntOpenFile(_) => EXIT_FILE_ERROR,
+            SrcFileErrors::BinFileWrongLen(_) => EXIT_INVALID_IMAGE,
+            SrcFileErrors::BinFileNotWords(_) => EXIT_INVALID_IMAGE,
+            SrcFileErrors::ImageOverflow(_, _) => EXIT_INVALID_IMAGE,
+            SrcFileErrors::AssembleError(_) => EXIT_ASSEMBLER_ERROR,
+            SrcFileErrors::InvalidSnapshot(_) => EXIT_INVALID_IMAGE,
+            SrcFileErrors::InvalidImage(_) => EXIT_INVALID_IMAGE,
+            SrcFileErrors::InvalidSnp(_) => EXIT_INVALID_IMAGE,
+            SrcFileErrors::InvalidSymbols(_, _) => EXIT_INVALID_IMAGE,
         }
     }
 }
@@ -34,13 +76,49 @@ impl RunError for SrcFileErrors {
 /// All the possible error encountered at runtime. 
 pub enum RunErrors {
     /// An error encountered getting the source file. 
-    SrcFileError(SrcFileErrors)
+    SrcFileError(SrcFileErrors),
+    /// The step budget was exceeded (steps executed, instruction address). 
+    StepsExceeded(usize, u16),
+    /// The model state repeated exactly (steps executed, instruction address). 
+    NoProgress(usize, u16),
+    /// The emulator faulted executing an instruction. 
+    EmulationError(Box<BabyErrors>),
+    /// Failed to write an output file. 
+    CouldntWriteFile(PathBuf),
+    /// The user interrupted execution (steps executed, instruction address). 
+    Interrupted(usize, u16),
 }
 
 impl RunError for RunErrors {
     fn describe(&self) -> String {
         match self {
-            RunErrors::SrcFileError(s) => format!("{}", s.describe()),
+            RunErrors::SrcFileError(s) => s.describe().to_string(),
+            RunErrors::StepsExceeded(s, a) => 
+                format!("Execution exceeded the step budget of `{}` instructions, halted at instruction address {:#06x}. ", s, a),
+            RunErrors::NoProgress(s, a) => 
+                format!("The model repeated a previous state after `{}` instructions at instruction address {:#06x}, the program will never stop. ", s, a),
+            RunErrors::EmulationError(e) => 
+                format!(
+                    "Emulation fault at instruction address {:#06x} ({}). \n{}", 
+                    e.at(), 
+                    e.get_instruction().get_instr_description(), 
+                    e.get_descriptor()
+                ),
+            RunErrors::CouldntWriteFile(s) => 
+                format!("Couldn't write to file: `{}`.", s.to_string_lossy()),
+            RunErrors::Interrupted(s, a) => 
+                format!("Execution was interrupted after `{}` instructions at instruction address {:#06x}. ", s, a),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            RunErrors::SrcFileError(s) => s.exit_code(),
+            RunErrors::StepsExceeded(_, _) => EXIT_STEPS_EXCEEDED,
+            RunErrors::NoProgress(_, _) => EXIT_NO_PROGRESS,
+            RunErrors::EmulationError(_) => EXIT_EMULATION_FAULT,
+            RunErrors::CouldntWriteFile(_) => EXIT_FILE_ERROR,
+            RunErrors::Interrupted(_, _) => EXIT_INTERRUPTED,
         }
     }
 }
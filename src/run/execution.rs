@@ -2,9 +2,17 @@ use baby_emulator::core::{BabyModel, instructions::BabyInstruction};
 use baby_emulator::core::errors::BabyErrors;
 use crate::args::Run;
 use crate::interface::Interface;
-use super::ProgramStack;
+use super::errors::RunErrors;
 use super::debug::{check_debug_session, DebugResult};
+use super::debug::tui::{check_tui_session, LiveView};
 use super::output::output_model;
+use super::trace::Tracer;
+use super::state::RunState;
+use super::profile::output_profile;
+use super::realtime::{Pacer, output_emulated_time};
+use super::clock::{Clock, instruction_beats};
+use super::display::output_display;
+use crate::symbols::Symbols;
 
 /// Iterates a model, returning the updated model plus any error encountered.  
 /// 
@@ -34,47 +42,207 @@ fn should_debug(model: &BabyModel, conf: &Run, err_opt: &Option<BabyErrors>) ->
     has_hit_bp || debug_on_err
 }
 
+/// Runs a debug session if the criteria for one has been met, resetting the 
+/// loop detector as the model may be modified during the session. 
 fn check_run_debug(
     debug_next: Option<()>,
     model: BabyModel, 
     conf: Run, 
     err_opt: &Option<BabyErrors>, 
+    detector: &mut LoopDetector,
+    state: &mut RunState,
     int: &impl Interface
 ) -> DebugResult {
     if should_debug(&model, &conf, err_opt) || debug_next.is_some() {
-        check_debug_session(&model, &conf, int)
+        detector.reset();
+        if conf.tui { check_tui_session(&model, &conf, state, int) }
+        else { check_debug_session(&model, &conf, state, int) }
     } else { DebugResult::Continue(model, conf) }
 }
 
-/// Runs a model indefinitely until a stop command is encountered. 
+/// Detects when a model returns to a previously seen state. 
+/// 
+/// Since execution is deterministic a repeated state proves the program 
+/// will never stop, uses Brent's cycle detection so only a single saved 
+/// state is held regardless of how long the program runs. 
+pub struct LoopDetector {
+    /// The last saved state to be compared against. 
+    saved: Option<BabyModel>,
+    /// The number of steps before the saved state is replaced. 
+    power: usize,
+    /// The number of steps since the saved state was replaced. 
+    length: usize,
+}
+
+impl LoopDetector {
+    /// Creates a new detector with no saved state. 
+    pub fn new() -> LoopDetector {
+        LoopDetector { saved: None, power: 1, length: 0 }
+    }
+
+    /// Forgets any saved state. 
+    pub fn reset(&mut self) {
+        *self = LoopDetector::new();
+    }
+
+    /// Checks a model against the saved state, returning true if 
+    /// it's a repeat of the saved state. 
+    /// 
+    /// # Parameters 
+    /// * `model` - The latest state of the model. 
+    /// 
+    pub fn check(&mut self, model: &BabyModel) -> bool {
+        if self.saved.as_ref() == Some(model) { return true; }
+        self.length += 1;
+        if self.length >= self.power {
+            self.saved = Some(model.clone());
+            self.power *= 2;
+            self.length = 0;
+        }
+        false
+    }
+}
+
+/// Gets the runtime error for an emulator error, if it's a fault. 
+/// 
+/// Encountering a stop instruction is the normal end of a program so isn't a fault. 
+/// 
+/// # Parameters 
+/// * `err_opt` - Possible errors returned from an execution. 
+/// 
+fn emulation_fault(err_opt: &Option<BabyErrors>) -> Option<RunErrors> {
+    match err_opt {
+        Some(BabyErrors::Stop(_)) | None => None,
+        Some(e) => Some(RunErrors::EmulationError(Box::new(e.clone()))),
+    }
+}
+
+/// Determines if the configured step budget has been used up. 
+/// 
+/// # Parameters 
+/// * `steps` - The number of instructions executed so far. 
+/// * `conf` - The configuration to check against. 
+/// 
+fn steps_exceeded(steps: usize, conf: &Run) -> bool {
+    match conf.max_steps {
+        Some(max) => steps >= max,
+        None => false
+    }
+}
+
+/// Creates a pacer if execution should run at the speed of the original machine. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to check against. 
+/// 
+fn pacer(conf: &Run) -> Option<Pacer> {
+    match (conf.realtime, conf.speed) {
+        (_, Some(speed)) => Some(Pacer::new(speed)),
+        (true, None) => Some(Pacer::new(1.0)),
+        (false, None) => None,
+    }
+}
+
+/// Runs a model until a stop command is encountered. 
+/// 
+/// # Parameters 
+/// * `conf` - The configuration to run against. 
+/// * `model` - The model to start running from. 
+/// * `symbols` - The tag names of the program's store lines. 
+/// * `interface` - The interface used for interacting with the user. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The program stopped or the user ended execution. 
+/// * [Err(RunErrors)] - The emulator faulted, the step budget was exceeded or the program can never stop. 
+/// 
+pub fn run_model(conf: Run, model: BabyModel, symbols: Symbols, interface: &impl Interface) -> Result<(), RunErrors> {
+    run_model_from(conf, model, symbols, Clock::new(), interface)
+}
+
+/// Runs a model until a stop command is encountered, carrying on counting 
+/// from a clock, such as one saved in a snapshot. 
 /// 
 /// # Parameters 
 /// * `conf` - The configuration to run against. 
-/// * `stack` - The program stack for the model to be initialised with. 
+/// * `model` - The model to start running from. 
+/// * `symbols` - The tag names of the program's store lines. 
+/// * `clock` - The steps & beats already executed. 
 /// * `interface` - The interface used for interacting with the user. 
 /// 
-pub fn run_model(conf: Run, stack: ProgramStack, interface: &impl Interface) {
-    let model = BabyModel::new_with_program(stack);
+/// # Returns 
+/// * [Ok(())] - The program stopped or the user ended execution. 
+/// * [Err(RunErrors)] - The emulator faulted, the step budget was exceeded or the program can never stop. 
+/// 
+pub fn run_model_from(conf: Run, model: BabyModel, symbols: Symbols, clock: Clock, interface: &impl Interface) -> Result<(), RunErrors> {
     let (mut model, mut conf) = (model.clone(), conf.clone());
-    let mut debug_next: Option<()> = None;
+    let mut debug_next: Option<()> = if conf.tui { Some(()) } else { None };
     let mut err_opt: Option<BabyErrors> = None;
-    loop {
-        let debug_res = check_run_debug(debug_next, model, conf, &err_opt, interface);
+    let mut detector = LoopDetector::new();
+    let mut tracer = conf.trace.as_ref().map(|out| Tracer::new(conf.trace_format, out.clone()));
+    let mut state = RunState::new(conf.history, conf.profile);
+    state.symbols = symbols;
+    state.clock = clock;
+    let mut pacer = pacer(&conf);
+    let mut live = if conf.tui { Some(LiveView::open(interface)) } else { None };
+    if tracer.is_some() && live.is_none() { interface.watch_interrupts(); }
+    let res = loop {
+        let debug_res = check_run_debug(debug_next, model, conf, &err_opt, &mut detector, &mut state, interface);
         
         (model, conf) = match debug_res {
             DebugResult::Continue(m, c) => { debug_next = None; (m, c) },
             DebugResult::SingleStep(m, c) => { debug_next = Some(()); (m, c) },
             DebugResult::End(m, c) => {
                 (model, conf) = (m, c);
-                break;
+                break match emulation_fault(&err_opt) {
+                    Some(e) => Err(e),
+                    None => Ok(())
+                };
             }
         };
+
+        if steps_exceeded(state.clock.steps, &conf) {
+            break Err(RunErrors::StepsExceeded(state.clock.steps, model.instruction_address));
+        }
         
+        state.history.push(state.clock, &model);
+        let before = model.clone();
         (model, err_opt) = iterate_model(&model);
+        let executed = BabyInstruction::from_number(before.instruction);
+        state.clock.tick(&executed);
 
-        if BabyInstruction::Stop == BabyInstruction::from_number(model.instruction) {
-            break;
+        if let Some(Err(e)) = tracer.as_mut().map(|t| t.record(state.clock.steps, &before, &model, interface)) {
+            break Err(e);
         }
-    }
-    output_model(&conf.output_regs, &conf.output_addr, conf.output_model, &model, interface);
+        if let Some(p) = &mut state.profile { p.record(&before, &model); }
+        if let Some(p) = &mut pacer { p.pace(instruction_beats(&executed), interface); }
+        if let Some(v) = &mut live { v.update(&model, &conf, &state, interface); }
+        if interface.interrupted() {
+            if live.is_some() { debug_next = Some(()); }
+            else { break Err(RunErrors::Interrupted(state.clock.steps, model.instruction_address)); }
+        }
+
+        if let Some(BabyErrors::Stop(_)) = err_opt {
+            break Ok(());
+        }
+
+        match emulation_fault(&err_opt) {
+            Some(e) if !conf.debug_on_err => break Err(e),
+            _ => ()
+        }
+
+        if conf.detect_loops && detector.check(&model) {
+            break Err(RunErrors::NoProgress(state.clock.steps, model.instruction_address));
+        }
+    };
+    if let Some(v) = &live { v.close(interface); }
+    output_model(&conf.output_regs, &conf.output_addr, conf.output_model, &model, &state, interface);
+    if conf.display { output_display(&model, interface); }
+    if let Some(p) = &state.profile { output_profile(p, interface); }
+    if pacer.is_some() { output_emulated_time(&state.clock, interface); }
+
+    let trace_res = match &mut tracer {
+        Some(t) => t.flush(interface),
+        None => Ok(())
+    };
+    res.and(trace_res)
 }
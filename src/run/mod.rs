@@ -1,9 +1,11 @@
 use baby_emulator::core::MEMORY_WORDS;
 use crate::interface::Interface;
-use super::args::Run;
+use super::args::{Run, Resume};
 use super::errors::Errors;
-use srcfile::get_src;
-use execution::run_model;
+use srcfile::{get_src, apply_initial_state};
+use execution::{run_model, run_model_from};
+use snapshot::load_snapshot;
+use errors::RunErrors;
 
 /// Contains possible errors encountered during runtime. 
 pub mod errors;
@@ -15,6 +17,20 @@ pub mod output;
 pub mod execution;
 /// Contains helpers for debugging a model. 
 pub mod debug;
+/// Contains helpers for recording an execution trace. 
+pub mod trace;
+/// Contains the runtime state kept whilst running a model. 
+pub mod state;
+/// Contains helpers for saving and resuming snapshots of a model. 
+pub mod snapshot;
+/// Contains helpers for profiling execution. 
+pub mod profile;
+/// Contains helpers for pacing execution to the original machine. 
+pub mod realtime;
+/// Contains helpers for counting the machine beats of executed instructions. 
+pub mod clock;
+/// Contains helpers for rendering the Williams tube display. 
+pub mod display;
 #[cfg(test)]
 mod tests;
 
@@ -29,8 +45,28 @@ pub type ProgramStack = [i32; MEMORY_WORDS];
 /// * `interface` - The interface to read and write to. 
 /// 
 pub fn execute(args: Run, interface: &impl Interface) -> Result<(), Errors> {
-    let src = get_src(&args, interface).map_err(|e| Errors::RuntimeError(e))?;
-    run_model(args, src, interface);
+    let (model, symbols) = get_src(&args, interface).map_err(Errors::RuntimeError)?;
+    let model = apply_initial_state(model, &args);
+    run_model(args, model, symbols, interface).map_err(Errors::RuntimeError)?;
+
+    Ok(())
+}
+
+/// Loads a snapshot and continues executing it based on given configuration. 
+/// 
+/// The snapshot's debug configuration is added to the given configuration, and 
+/// the steps & beats executed before it was saved carry on being counted. 
+/// 
+/// # Parameters 
+/// * `args` - The configuration to be used. 
+/// * `interface` - The interface to read and write to. 
+/// 
+pub fn resume(args: Resume, interface: &impl Interface) -> Result<(), Errors> {
+    let snapshot = load_snapshot(&args.snapshot, interface)
+        .map_err(|e| Errors::RuntimeError(RunErrors::SrcFileError(e)))?;
+    let args = snapshot.apply(&args.run_conf());
+    run_model_from(args, snapshot.model, snapshot.symbols, snapshot.clock, interface)
+        .map_err(Errors::RuntimeError)?;
 
     Ok(())
 }
@@ -2,6 +2,8 @@ use baby_emulator::core::{MEMORY_WORDS, BabyModel, instructions::BabyInstruction
 use strum::IntoEnumIterator;
 use crate::args::Registers;
 use crate::interface::Interface;
+use crate::symbols::Symbols;
+use super::state::RunState;
 
 
 /// Outputs a register of a model, formatted, to an interface. 
@@ -9,30 +11,64 @@ use crate::interface::Interface;
 /// # Parameters 
 /// * `regs` - The register to be outputted. 
 /// * `model` - The model to be read. 
+/// * `state` - The runtime state to read pseudo-registers & tag names from. 
 /// * `int` - The interface to output to. 
 ///  
-pub fn output_register(regs: &Registers, model: &BabyModel, int: &impl Interface) {
+pub fn output_register(regs: &Registers, model: &BabyModel, state: &RunState, int: &impl Interface) {
     match regs {
         Registers::Accumulator => int.log_msg(format!("{:#010x}", model.accumulator)),
-        Registers::InstructionAddress => int.log_msg(format!("{:#010x}", model.instruction_address)),
+        Registers::InstructionAddress => int.log_msg(with_tag(
+            format!("{:#010x}", model.instruction_address), 
+            model.instruction_address as usize, 
+            &state.symbols
+        )),
         Registers::Instruction => 
             int.log_msg(format!(
                 "{:#010x} ({})", 
                 model.instruction, 
                 BabyInstruction::from_number(model.instruction).get_instr_description()
             )),
+        Registers::Beats => int.log_msg(format!("{} beats", state.clock.beats)),
     }
 }
 
+/// Appends the tag name bound to an address in brackets, if it has one. 
+/// 
+/// # Parameters 
+/// * `line` - The line to be appended to. 
+/// * `addr` - The address to look up. 
+/// * `symbols` - The tag names to look up from. 
+/// 
+pub fn with_tag(line: String, addr: usize, symbols: &Symbols) -> String {
+    match symbols.name_of(addr & 0x1F) {
+        Some(n) => format!("{} ({})", line, n),
+        None => line,
+    }
+}
+
+/// Outputs a memory address of a model, formatted & labelled with its tag name, to an interface. 
+/// 
+/// # Parameters 
+/// * `addr` - The memory address to be outputted. 
+/// * `model` - The model to be read. 
+/// * `symbols` - The tag names to label the memory address with. 
+/// * `int` - The interface to output to. 
+///  
+pub fn output_memory_address(addr: usize, model: &BabyModel, symbols: &Symbols, int: &impl Interface) {
+    let line = format!("{:#04x}: {:#010x}", addr, model.main_store[addr & 0x1F]);
+    int.log_msg(with_tag(line, addr, symbols));
+}
+
 /// Outputs the all the registers of a model, formatted to an interface. 
 /// 
 /// # Parameters 
 /// * `model` - The model to be read. 
+/// * `state` - The runtime state to read pseudo-registers & tag names from. 
 /// * `int` - The interface to output to. 
 ///  
-pub fn output_all_registers(model: &BabyModel, int: &impl Interface) {
+pub fn output_all_registers(model: &BabyModel, state: &RunState, int: &impl Interface) {
     for v in Registers::iter() {
-        output_register(&v, model, int);
+        output_register(&v, model, state, int);
     }
 }
 
@@ -40,11 +76,12 @@ pub fn output_all_registers(model: &BabyModel, int: &impl Interface) {
 /// 
 /// # Parameters 
 /// * `model` - The model to be read. 
+/// * `symbols` - The tag names to label memory addresses with. 
 /// * `int` - The interface to output to. 
 ///  
-pub fn output_all_memory(model: &BabyModel, int: &impl Interface) {
+pub fn output_all_memory(model: &BabyModel, symbols: &Symbols, int: &impl Interface) {
     for v in 0..MEMORY_WORDS {
-        int.log_msg(format!("{:#04x}: {:#010x}", v, model.main_store[v & 0x1F]));
+        output_memory_address(v, model, symbols, int);
     }
 }
 
@@ -55,21 +92,21 @@ pub fn output_all_memory(model: &BabyModel, int: &impl Interface) {
 /// * `memory_addrs` - Any memory addresses to be outputted. 
 /// * `output_model` - Output everything in the model. 
 /// * `model` - The model to be read. 
+/// * `state` - The runtime state to read pseudo-registers & tag names from. 
 /// * `int` - The interface to be outputted to. 
 /// 
 pub fn output_model(
-    registers: &Vec<Registers>, 
-    memory_addrs: &Vec<usize>, 
+    registers: &[Registers], 
+    memory_addrs: &[usize], 
     output_model: bool, 
     model: &BabyModel,
+    state: &RunState,
     int: &impl Interface
 ) {
     if output_model {
         int.log_msg(model.core_dump());
         return;
     }
-    registers.iter().for_each(|v| output_register(v, &model, int));
-    memory_addrs.iter().for_each(|v| 
-        int.log_msg(format!("{:#04x}: {:#010x}", v, model.main_store[v & 0x1F]))
-    );
+    registers.iter().for_each(|v| output_register(v, model, state, int));
+    memory_addrs.iter().for_each(|v| output_memory_address(*v, model, &state.symbols, int));
 }
@@ -0,0 +1,93 @@
+use baby_emulator::core::{BabyModel, MEMORY_WORDS, instructions::BabyInstruction};
+use crate::interface::Interface;
+use crate::notation::{mnemonic, has_operand};
+use super::trace::TraceStep;
+
+
+/// Every instruction the Baby can execute, in the order they're reported. 
+pub const INSTRUCTIONS: [BabyInstruction; 7] = [
+    BabyInstruction::Jump(0),
+    BabyInstruction::RelativeJump(0),
+    BabyInstruction::Negate(0),
+    BabyInstruction::Store(0),
+    BabyInstruction::Subtract(0),
+    BabyInstruction::SkipNextIfNegative,
+    BabyInstruction::Stop,
+];
+
+/// Gets the position of an instruction in [INSTRUCTIONS]. 
+fn instruction_index(instruction: &BabyInstruction) -> usize {
+    INSTRUCTIONS.iter()
+        .position(|v| mnemonic(v) == mnemonic(instruction))
+        .unwrap_or(INSTRUCTIONS.len() - 1)
+}
+
+/// Counts of how a program used each store line and instruction. 
+pub struct Profile {
+    /// The number of times each store line was executed. 
+    pub executed: [usize; MEMORY_WORDS],
+    /// The number of times each word was read as an operand. 
+    pub reads: [usize; MEMORY_WORDS],
+    /// The number of times each word was written to. 
+    pub writes: [usize; MEMORY_WORDS],
+    /// The number of times each instruction was executed, ordered as [INSTRUCTIONS]. 
+    pub instructions: [usize; INSTRUCTIONS.len()],
+}
+
+impl Profile {
+    /// Creates a new profile with all counts at zero. 
+    pub fn new() -> Profile {
+        Profile {
+            executed: [0; MEMORY_WORDS],
+            reads: [0; MEMORY_WORDS],
+            writes: [0; MEMORY_WORDS],
+            instructions: [0; INSTRUCTIONS.len()],
+        }
+    }
+
+    /// Records an executed instruction, a store counts as a write to the 
+    /// address held at its operand even if the value is already there. 
+    /// 
+    /// # Parameters 
+    /// * `before` - The model before the instruction was executed. 
+    /// * `after` - The model after the instruction was executed. 
+    /// 
+    pub fn record(&mut self, before: &BabyModel, after: &BabyModel) {
+        let step = TraceStep::new(0, before, after);
+        self.executed[step.address as usize & 0x1F] += 1;
+        self.instructions[instruction_index(&step.instruction)] += 1;
+        if has_operand(&step.instruction) {
+            self.reads[step.instruction.get_operand()] += 1;
+        }
+        if let Some((addr, _)) = step.store_write {
+            self.writes[addr] += 1;
+        }
+    }
+
+    /// Formats the profile as table rows, including only store lines that were used. 
+    pub fn report(&self) -> Vec<String> {
+        let total: usize = self.instructions.iter().sum();
+        let mut res = vec![format!("{:<8} {:>10} {:>10} {:>10}", "Address", "Executed", "Reads", "Writes")];
+        for i in 0..MEMORY_WORDS {
+            if self.executed[i] + self.reads[i] + self.writes[i] == 0 { continue; }
+            res.push(format!("{:<8} {:>10} {:>10} {:>10}", format!("{:#04x}", i), self.executed[i], self.reads[i], self.writes[i]));
+        }
+        res.push(format!("{:<8} {:>10} {:>10}", "Instr", "Count", "Share"));
+        for (i, instr) in INSTRUCTIONS.iter().enumerate() {
+            let share = if total == 0 { 0.0 } 
+                else { self.instructions[i] as f64 * 100.0 / total as f64 };
+            res.push(format!("{:<8} {:>10} {:>9.1}%", mnemonic(instr), self.instructions[i], share));
+        }
+        res
+    }
+}
+
+/// Outputs a profile, formatted as a table, to an interface. 
+/// 
+/// # Parameters 
+/// * `profile` - The profile to be outputted. 
+/// * `int` - The interface to output to. 
+/// 
+pub fn output_profile(profile: &Profile, int: &impl Interface) {
+    profile.report().into_iter().for_each(|v| int.log_msg(v));
+}
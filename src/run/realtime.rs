@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+use crate::interface::Interface;
+use super::clock::{Clock, BEAT_DURATION};
+
+
+/// How far behind schedule execution can fall (e.g. whilst paused in a debug 
+/// session) before the schedule is restarted rather than caught up with. 
+const MAX_LAG: Duration = Duration::from_millis(100);
+
+/// The shortest delay worth sleeping for, shorter delays are accumulated 
+/// over following steps to avoid the overhead of sleeping every step. 
+const MIN_SLEEP: Duration = Duration::from_millis(1);
+
+/// Paces execution to the speed of the original machine. 
+pub struct Pacer {
+    /// The host time each machine beat should take. 
+    pub beat: Duration,
+    /// The host time the last instruction was due to finish. 
+    due: Option<Instant>,
+}
+
+impl Pacer {
+    /// Creates a new pacer. 
+    /// 
+    /// # Parameters 
+    /// * `speed` - A multiplier on the original machine's speed. 
+    /// 
+    pub fn new(speed: f64) -> Pacer {
+        let beat = Duration::from_secs_f64(BEAT_DURATION.as_secs_f64() / speed);
+        Pacer { beat, due: None }
+    }
+
+    /// Waits until the last instruction is due to finish. 
+    /// 
+    /// # Parameters 
+    /// * `beats` - The number of beats the last instruction took. 
+    /// * `int` - The interface used to wait. 
+    /// 
+    pub fn pace(&mut self, beats: usize, int: &impl Interface) {
+        let now = Instant::now();
+        let period = self.beat * beats as u32;
+        let due = match self.due {
+            Some(d) if d + MAX_LAG >= now => d + period,
+            _ => now + period,
+        };
+        if due > now + MIN_SLEEP {
+            int.sleep(due - now);
+        }
+        self.due = Some(due);
+    }
+}
+
+/// Formats a duration as hours, minutes & seconds, omitting leading zero units. 
+/// 
+/// # Parameters 
+/// * `duration` - The duration to be formatted. 
+/// 
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, mins) = (secs / 3600, (secs / 60) % 60);
+    let secs = (secs % 60) as f64 + duration.subsec_nanos() as f64 / 1e9;
+    match (hours, mins) {
+        (0, 0) => format!("{:.3}s", secs),
+        (0, m) => format!("{}m {:.3}s", m, secs),
+        (h, m) => format!("{}h {}m {:.3}s", h, m, secs),
+    }
+}
+
+/// Outputs the time the original machine would have taken to run a program. 
+/// 
+/// # Parameters 
+/// * `clock` - The clock of the run. 
+/// * `int` - The interface to output to. 
+/// 
+pub fn output_emulated_time(clock: &Clock, int: &impl Interface) {
+    int.log_msg(format!(
+        "Emulated time: {} ({} instructions, {} beats). ", 
+        format_duration(clock.time()), clock.steps, clock.beats
+    ));
+}
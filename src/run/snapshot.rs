@@ -0,0 +1,217 @@
+use std::path::Path;
+use baby_emulator::core::{BabyModel, MEMORY_WORDS};
+use crate::args::{Run, Registers};
+use crate::interface::Interface;
+use super::debug::utils::{parse_memory_addresses, parse_registers};
+use super::errors::{RunErrors, SrcFileErrors};
+use super::clock::Clock;
+use super::state::RunState;
+use crate::symbols::Symbols;
+
+
+/// The first line of every snapshot file, identifying the format and version. 
+pub const SNAPSHOT_HEADER: &str = "ssemu-snapshot 1";
+
+/// The full state of a model plus the debug configuration it was running with. 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snapshot {
+    /// The state of the model. 
+    pub model: BabyModel,
+    /// The steps & beats executed before the snapshot was saved. 
+    pub clock: Clock,
+    /// The tag names of the program's store lines. 
+    pub symbols: Symbols,
+    /// Output the whole model when execution stops or a breakpoint is hit. 
+    pub output_model: bool,
+    /// The addresses to break at. 
+    pub break_addr: Vec<usize>,
+    /// The memory addresses to output. 
+    pub output_addr: Vec<usize>,
+    /// The registers to output. 
+    pub output_regs: Vec<Registers>,
+}
+
+/// Formats a list of addresses as comma separated hex values. 
+fn format_addresses(addrs: &[usize]) -> String {
+    addrs.iter()
+        .map(|v| format!("{:#04x}", v))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Parses a hex value such as `0xfffffffb` as the two's complement bits of an [i32]. 
+fn parse_hex_word(value: &str) -> Result<i32, String> {
+    let value = value.trim();
+    u32::from_str_radix(&value.replacen("0x", "", 1), 16)
+        .map(|v| v as i32)
+        .map_err(|_| format!("Invalid value `{}`. ", value))
+}
+
+/// Parses a boolean written as `true` or `false`. 
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        v => Err(format!("Invalid boolean `{}`, expected `true` or `false`. ", v))
+    }
+}
+
+/// Parses a decimal count such as the steps executed. 
+fn parse_count(value: &str) -> Result<usize, String> {
+    value.trim().parse::<usize>()
+        .map_err(|_| format!("Invalid count `{}`. ", value.trim()))
+}
+
+impl Snapshot {
+    /// Captures a snapshot of a model, its clock, its symbols and its debug configuration. 
+    /// 
+    /// # Parameters 
+    /// * `model` - The model to be captured. 
+    /// * `clock` - The steps & beats executed so far. 
+    /// * `symbols` - The tag names of the program's store lines. 
+    /// * `conf` - The configuration to be captured. 
+    /// 
+    pub fn new(model: &BabyModel, clock: &Clock, symbols: &Symbols, conf: &Run) -> Snapshot {
+        Snapshot {
+            model: model.clone(),
+            clock: *clock,
+            symbols: symbols.clone(),
+            output_model: conf.output_model,
+            break_addr: conf.break_addr.clone(),
+            output_addr: conf.output_addr.clone(),
+            output_regs: conf.output_regs.clone(),
+        }
+    }
+
+    /// Formats the snapshot as text to be saved to a file. 
+    pub fn serialise(&self) -> String {
+        let regs = self.output_regs.iter()
+            .map(|v| format!("{:?}", v).to_lowercase())
+            .collect::<Vec<String>>()
+            .join(", ");
+        let mut res = format!("{}\n", SNAPSHOT_HEADER);
+        res += &format!("accumulator: {:#010x}\n", self.model.accumulator);
+        res += &format!("instruction: {:#06x}\n", self.model.instruction);
+        res += &format!("instruction-address: {:#06x}\n", self.model.instruction_address);
+        res += &format!("steps: {}\n", self.clock.steps);
+        res += &format!("beats: {}\n", self.clock.beats);
+        res += &format!("output-model: {}\n", self.output_model);
+        res += &format!("break-addrs: {}\n", format_addresses(&self.break_addr));
+        res += &format!("debug-addrs: {}\n", format_addresses(&self.output_addr));
+        res += &format!("debug-regs: {}\n", regs);
+        self.symbols.serialise().lines().for_each(|v| res += &format!("symbol: {}\n", v));
+        for (i, v) in self.model.main_store.iter().enumerate() {
+            res += &format!("{:#04x}: {:#010x}\n", i, v);
+        }
+        res
+    }
+
+    /// Parses a snapshot from text. 
+    /// 
+    /// # Parameters 
+    /// * `text` - The text to be parsed. 
+    /// 
+    /// # Returns 
+    /// * [Ok(Snapshot)] - The parsed snapshot. 
+    /// * [Err(String)] - A formatted error message if the text is invalid. 
+    /// 
+    pub fn parse(text: &str) -> Result<Snapshot, String> {
+        let mut lines = text.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+        if lines.next() != Some(SNAPSHOT_HEADER) {
+            return Err(format!("Missing header `{}`. ", SNAPSHOT_HEADER));
+        }
+        let mut res = Snapshot {
+            model: BabyModel::new(),
+            clock: Clock::new(),
+            symbols: Symbols::new(),
+            output_model: false,
+            break_addr: vec![],
+            output_addr: vec![],
+            output_regs: vec![],
+        };
+        let mut seen = [false; MEMORY_WORDS];
+        let mut symbols = String::new();
+        for line in lines {
+            let (key, value) = line.split_once(":")
+                .ok_or(format!("Invalid line `{}`. ", line))?;
+            let value = value.trim();
+            match key.trim() {
+                "accumulator" => res.model.accumulator = parse_hex_word(value)?,
+                "instruction" => res.model.instruction = parse_hex_word(value)? as u16,
+                "instruction-address" => res.model.instruction_address = parse_hex_word(value)? as u16 & 0x1F,
+                "steps" => res.clock.steps = parse_count(value)?,
+                "beats" => res.clock.beats = parse_count(value)?,
+                "output-model" => res.output_model = parse_bool(value)?,
+                "break-addrs" if !value.is_empty() => res.break_addr = parse_memory_addresses(value.to_owned())?,
+                "debug-addrs" if !value.is_empty() => res.output_addr = parse_memory_addresses(value.to_owned())?,
+                "debug-regs" if !value.is_empty() => res.output_regs = parse_registers(value)
+                    .map_err(|e| format!("Invalid register name `{}`. ", e))?,
+                "break-addrs" | "debug-addrs" | "debug-regs" => (),
+                "symbol" => symbols += &format!("{}\n", value),
+                k if k.starts_with("0x") => {
+                    let addr = parse_hex_word(k)? as usize;
+                    if addr >= MEMORY_WORDS { return Err(format!("Invalid store address `{}`. ", k)); }
+                    if seen[addr] { return Err(format!("Duplicate store address `{}`. ", k)); }
+                    res.model.main_store[addr] = parse_hex_word(value)?;
+                    seen[addr] = true;
+                },
+                k => return Err(format!("Unknown key `{}`. ", k))
+            }
+        }
+        if let Some(addr) = seen.iter().position(|v| !v) {
+            return Err(format!("Missing store address `{:#04x}`, expected {} store words. ", addr, MEMORY_WORDS));
+        }
+        res.symbols = Symbols::parse(&symbols).map_err(|e| format!("Invalid symbols. {}", e))?;
+        Ok(res)
+    }
+
+    /// Applies the captured debug configuration on top of a run configuration. 
+    /// 
+    /// # Parameters 
+    /// * `conf` - The configuration to add to. 
+    /// 
+    pub fn apply(&self, conf: &Run) -> Run {
+        let mut conf = conf.clone();
+        conf.output_model = conf.output_model || self.output_model;
+        self.break_addr.iter().for_each(|v| if !conf.break_addr.contains(v) { conf.break_addr.push(*v) });
+        self.output_addr.iter().for_each(|v| if !conf.output_addr.contains(v) { conf.output_addr.push(*v) });
+        self.output_regs.iter().for_each(|v| if !conf.output_regs.contains(v) { conf.output_regs.push(*v) });
+        conf
+    }
+}
+
+/// Saves a snapshot of a model, its runtime state and its debug configuration to an interface. 
+/// 
+/// # Parameters 
+/// * `out` - The path of the snapshot file. 
+/// * `model` - The model to be saved. 
+/// * `state` - The runtime state holding the clock & symbols to be saved. 
+/// * `conf` - The configuration to be saved. 
+/// * `int` - The interface to write to. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The snapshot was written. 
+/// * [Err(RunErrors)] - The snapshot file couldn't be written. 
+/// 
+pub fn save_snapshot(out: &Path, model: &BabyModel, state: &RunState, conf: &Run, int: &impl Interface) -> Result<(), RunErrors> {
+    let data = Snapshot::new(model, &state.clock, &state.symbols, conf).serialise();
+    int.write_fs_bytes(data.into_bytes(), out)
+        .map_err(|_| RunErrors::CouldntWriteFile(out.to_path_buf()))
+}
+
+/// Reads and parses a snapshot from an interface. 
+/// 
+/// # Parameters 
+/// * `source` - The path of the snapshot file. 
+/// * `int` - The interface to read from. 
+/// 
+/// # Returns 
+/// * [Ok(Snapshot)] - The parsed snapshot. 
+/// * [Err(SrcFileErrors)] - The snapshot file couldn't be read or is invalid. 
+/// 
+pub fn load_snapshot(source: &Path, int: &impl Interface) -> Result<Snapshot, SrcFileErrors> {
+    let text = int.read_fs_string(source)
+        .map_err(|_| SrcFileErrors::CouldntOpenFile(source.to_path_buf()))?;
+    Snapshot::parse(&text)
+        .map_err(SrcFileErrors::InvalidSnapshot)
+}
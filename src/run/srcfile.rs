@@ -1,10 +1,15 @@
-use std::path::PathBuf;
-use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
-use baby_emulator::assembler::assemble;
+use std::path::Path;
+use baby_emulator::core::{BabyModel, MEMORY_WORDS, instructions::BabyInstruction};
+use crate::assemble::diagnostics::assemble_source;
+use crate::assemble::preprocess::{Preprocessed, read_source};
+use crate::assemble::errors::AsmErrors;
 use super::ProgramStack;
 use super::errors::{SrcFileErrors, RunErrors};
-use crate::args::{Run, ExecuteFrom};
+use crate::args::{Run, ExecuteFrom, Endianness};
 use crate::interface::Interface;
+use crate::symbols::{Symbols, symbol_path};
+use crate::snp::parse_snp;
+use crate::image::Image;
 
 
 /// Reads a file from the interface and attempts to assemble it into a program stack. 
@@ -19,25 +24,41 @@ use crate::interface::Interface;
 /// * [Err(SrcFileErrors)] - An error details. 
 /// 
 fn get_src_from_asm(
-    source: &PathBuf, 
+    source: &Path, 
     og_notation: bool, 
     interface: &impl Interface
 ) -> Result<ProgramStack, SrcFileErrors> {
-    let asm = interface.read_fs_string(source)
-        .map_err(|_| SrcFileErrors::CouldntOpenFile(source.clone()))?;
+    let pre = read_preprocessed(source, interface)?;
 
-    let res = assemble(&asm, og_notation)
-        .map_err(|e| SrcFileErrors::AssembleError(e))?;
+    let res = assemble_source(&pre, og_notation)
+        .map_err(SrcFileErrors::AssembleError)?;
 
     Ok(BabyInstruction::to_numbers(res))
 }
 
+/// Reads an asm source file from an interface and expands its preprocessor directives. 
+/// 
+/// # Parameters 
+/// * `source` - The path to the asm file. 
+/// * `interface` - The interface to read from. 
+/// 
+/// # Returns 
+/// * [Ok(Preprocessed)] - The expanded source. 
+/// * [Err(SrcFileErrors)] - The file couldn't be read or preprocessed. 
+/// 
+fn read_preprocessed(source: &Path, interface: &impl Interface) -> Result<Preprocessed, SrcFileErrors> {
+    read_source(source, interface).map_err(|e| match e {
+        AsmErrors::AssembleError(s) => SrcFileErrors::AssembleError(s),
+        _ => SrcFileErrors::CouldntOpenFile(source.to_path_buf()),
+    })
+}
+
 /// Provides a function to read an i32 word from 4 bytes in an array. 
 /// 
 /// Takes an array of the bytes, returning a closure that accepts 
 /// the index of the word and returns the generated word. 
 /// 
-pub fn read_word<'a>(raw: &'a Vec<u8>) -> impl Fn(usize) -> i32 + 'a {
+pub fn read_word<'a>(raw: &'a [u8]) -> impl Fn(usize) -> i32 + 'a {
     |i: usize| (0..4).fold(0, |val, j| {
         let index = (i * 4) + j;
         let value = *raw.get(index).unwrap_or(&0) as i32;
@@ -54,41 +75,304 @@ pub fn read_words(raw: Vec<u8>) -> [i32; MEMORY_WORDS] {
     core::array::from_fn(read_word(&raw))
 }
 
-/// Reads a binary file from the interface and attempts to fit it into a program stack. 
+/// Reads groups of 4 bytes into i32 words in a given byte order, ignoring any 
+/// trailing bytes that don't make a whole word. 
 /// 
 /// # Parameters 
-/// * `source` - The binary file source. 
+/// * `raw` - The raw bytes. 
+/// * `endian` - The byte order of each word. 
+/// 
+pub fn read_words_endian(raw: &[u8], endian: Endianness) -> Vec<i32> {
+    raw.chunks_exact(4)
+        .map(|v| {
+            let bytes = [v[0], v[1], v[2], v[3]];
+            match endian {
+                Endianness::Big => i32::from_be_bytes(bytes),
+                Endianness::Little => i32::from_le_bytes(bytes),
+            }
+        })
+        .collect()
+}
+
+/// Loads a binary image into a program stack at a base address. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to load into. 
+/// * `raw` - The raw bytes of the image. 
+/// * `base` - The store address of the first word of the image. 
+/// * `partial` - Whether the image may be shorter than the store. 
+/// * `endian` - The byte order of each word. 
+/// 
+/// # Returns 
+/// * [Ok(())] - The image was loaded. 
+/// * [Err(SrcFileErrors)] - The image was the wrong length or doesn't fit in the store. 
+/// 
+// `is_multiple_of` would raise the minimum supported Rust version to 1.87. 
+#[allow(clippy::manual_is_multiple_of)]
+pub fn load_image(
+    stack: &mut ProgramStack, 
+    raw: &[u8], 
+    base: usize, 
+    partial: bool, 
+    endian: Endianness
+) -> Result<(), SrcFileErrors> {
+    if !partial && raw.len() != MEMORY_WORDS * 4 {
+        return Err(SrcFileErrors::BinFileWrongLen(raw.len()))
+    }
+    if raw.len() % 4 != 0 {
+        return Err(SrcFileErrors::BinFileNotWords(raw.len()))
+    }
+
+    let words = read_words_endian(raw, endian);
+    if base + words.len() > MEMORY_WORDS {
+        return Err(SrcFileErrors::ImageOverflow(base, words.len()))
+    }
+    stack[base..base + words.len()].copy_from_slice(&words);
+
+    Ok(())
+}
+
+/// Parses an image file, reporting the program it contains. 
+/// 
+/// # Parameters 
+/// * `raw` - The raw bytes of the image file. 
+/// * `interface` - The interface to report the program to. 
+/// 
+/// # Returns 
+/// * [Ok(BabyModel)] - The model with the image's store & initial state. 
+/// * [Err(SrcFileErrors)] - The image was invalid. 
+/// 
+fn from_image(raw: &[u8], interface: &impl Interface) -> Result<BabyModel, SrcFileErrors> {
+    let image = Image::parse(raw).map_err(SrcFileErrors::InvalidImage)?;
+    interface.log_msg(format!(
+        "Loaded image `{}` ({} notation, entry {:#04x}). ", 
+        image.name, 
+        if image.og_notation { "original" } else { "modern" }, 
+        image.instruction_address
+    ));
+    Ok(image.model())
+}
+
+/// Reads a binary file from the interface and attempts to fit it into a model. 
+/// 
+/// Image files are loaded with their initial state, raw binary files at the 
+/// configured address, zeroing the rest of the store if the image is partial. 
+/// 
+/// # Parameters 
+/// * `config` - The configuration containing the binary file source & how to load it. 
 /// * `interface` - The interface to be used to read the binary source file. 
 /// 
 /// # Returns 
+/// * [Ok(BabyModel)] - The successfully read program. 
+/// * [Err(SrcFileErrors)] - An error details. 
+/// 
+fn from_bin(config: &Run, interface: &impl Interface) -> Result<BabyModel, SrcFileErrors> {
+    let raw = interface.read_fs_bytes(&config.src)
+        .map_err(|_| SrcFileErrors::CouldntOpenFile(config.src.clone()))?;
+
+    if Image::is_image(&raw) {
+        return from_image(&raw, interface);
+    }
+
+    let mut res = [0; MEMORY_WORDS];
+    let partial = config.partial || config.load_addr != 0;
+    load_image(&mut res, &raw, config.load_addr, partial, config.endian)?;
+
+    Ok(BabyModel::new_with_program(res))
+}
+
+/// Reads each of the configured overlay images from the interface and loads 
+/// them over a model's store, in order, refetching the current instruction. 
+/// 
+/// # Parameters 
+/// * `model` - The model to load over. 
+/// * `config` - The configuration containing the overlays. 
+/// * `interface` - The interface to be used to read the overlay images. 
+/// 
+/// # Returns 
+/// * [Ok(BabyModel)] - The model with the overlays loaded. 
+/// * [Err(SrcFileErrors)] - An overlay couldn't be read or doesn't fit in the store. 
+/// 
+fn apply_overlays(
+    model: BabyModel, 
+    config: &Run, 
+    interface: &impl Interface
+) -> Result<BabyModel, SrcFileErrors> {
+    let mut res = model;
+    for overlay in &config.overlay {
+        let raw = interface.read_fs_bytes(&overlay.path)
+            .map_err(|_| SrcFileErrors::CouldntOpenFile(overlay.path.clone()))?;
+        load_image(&mut res.main_store, &raw, overlay.addr, true, config.endian)?;
+    }
+    res.instruction = res.main_store[res.instruction_address as usize & 0x1F] as u16;
+    Ok(res)
+}
+
+/// Reads a `.snp` file from the interface and attempts to parse it into a program stack. 
+/// 
+/// # Parameters 
+/// * `source` - The `.snp` file source. 
+/// * `interface` - The interface to be used to read the `.snp` file. 
+/// 
+/// # Returns 
 /// * [Ok(ProgramStack)] - The successfully read program. 
 /// * [Err(SrcFileErrors)] - An error details. 
 /// 
-fn from_bin(source: &PathBuf, interface: &impl Interface) -> Result<ProgramStack, SrcFileErrors> {
-    let raw = interface.read_fs_bytes(source)
-        .map_err(|_| SrcFileErrors::CouldntOpenFile(source.clone()))?;
+fn from_snp(source: &Path, interface: &impl Interface) -> Result<ProgramStack, SrcFileErrors> {
+    let text = interface.read_fs_string(source)
+        .map_err(|_| SrcFileErrors::CouldntOpenFile(source.to_path_buf()))?;
 
-    if raw.len() != MEMORY_WORDS * 4 {
-        return Err(SrcFileErrors::BinFileWrongLen(raw.len()))
+    parse_snp(&text).map_err(SrcFileErrors::InvalidSnp)
+}
+
+/// Reads the symbol table for a binary or `.snp` file from the symbol file next to it. 
+/// 
+/// The symbol file is optional, an empty table is returned if it can't be read. 
+/// 
+/// # Parameters 
+/// * `source` - The binary file source. 
+/// * `interface` - The interface to be used to read the symbol file. 
+/// 
+/// # Returns 
+/// * [Ok(Symbols)] - The symbol table, empty if there's no symbol file. 
+/// * [Err(SrcFileErrors)] - The symbol file was malformed. 
+/// 
+fn symbols_from_bin(source: &Path, interface: &impl Interface) -> Result<Symbols, SrcFileErrors> {
+    let path = symbol_path(source);
+    match interface.read_fs_string(&path) {
+        Ok(v) => Symbols::parse(&v).map_err(|e| SrcFileErrors::InvalidSymbols(path, e)),
+        Err(_) => Ok(Symbols::new()),
+    }
+}
+
+/// Attempts to get the symbol table of a program source in a given format. 
+/// 
+/// Asm sources take the tags in the source, binary & `.snp` sources read the 
+/// symbol file next to them if there is one. 
+/// 
+/// # Parameters 
+/// * `source` - The source file. 
+/// * `format` - The format of the source file. 
+/// * `og_notation` - Whether to use original asm notation for asm sources. 
+/// * `interface` - The interface to use. 
+/// 
+pub fn get_symbols(
+    source: &Path, 
+    format: ExecuteFrom, 
+    og_notation: bool, 
+    interface: &impl Interface
+) -> Result<Symbols, SrcFileErrors> {
+    match format {
+        ExecuteFrom::Asm => read_preprocessed(source, interface)
+            .map(|v| Symbols::from_asm(&v.text(), og_notation)),
+        ExecuteFrom::Auto | ExecuteFrom::Bin | ExecuteFrom::Snp => symbols_from_bin(source, interface),
     }
+}
 
-    let res = read_words(raw);
+/// Gets the name of a source format as used on the command line. 
+/// 
+/// # Parameters 
+/// * `format` - The format to be named. 
+/// 
+pub fn format_name(format: ExecuteFrom) -> &'static str {
+    match format {
+        ExecuteFrom::Auto => "auto",
+        ExecuteFrom::Asm => "asm",
+        ExecuteFrom::Bin => "bin",
+        ExecuteFrom::Snp => "snp",
+    }
+}
 
+/// Detects the format of a source file, first from its file extension, then 
+/// from its contents. 
+/// 
+/// Contents that start with the image magic or aren't text are binary, text that parses as a `.snp` file is 
+/// `.snp`, anything else is asm. 
+/// 
+/// # Parameters 
+/// * `source` - The source file. 
+/// * `interface` - The interface to be used to read the source file. 
+/// 
+/// # Returns 
+/// * [Ok(ExecuteFrom)] - The detected format, never [ExecuteFrom::Auto]. 
+/// * [Err(SrcFileErrors)] - The source file couldn't be read. 
+/// 
+pub fn detect_format(source: &Path, interface: &impl Interface) -> Result<ExecuteFrom, SrcFileErrors> {
+    let extension = source.extension()
+        .map(|v| v.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "asm" | "s" => return Ok(ExecuteFrom::Asm),
+        "bin" | "img" => return Ok(ExecuteFrom::Bin),
+        "snp" => return Ok(ExecuteFrom::Snp),
+        _ => ()
+    };
+
+    let raw = interface.read_fs_bytes(source)
+        .map_err(|_| SrcFileErrors::CouldntOpenFile(source.to_path_buf()))?;
+    if Image::is_image(&raw) {
+        return Ok(ExecuteFrom::Bin);
+    }
+    let res = match String::from_utf8(raw) {
+        Ok(t) if t.contains('\0') => ExecuteFrom::Bin,
+        Ok(t) if !t.trim().is_empty() && parse_snp(&t).is_ok() => ExecuteFrom::Snp,
+        Ok(_) => ExecuteFrom::Asm,
+        Err(_) => ExecuteFrom::Bin,
+    };
     Ok(res)
 }
 
-/// Attempts to get the program source based on the configuration and 
-/// a given file system interface. 
+/// Attempts to get the program source as a model ready to run and its symbol 
+/// table based on the configuration and a given file system interface. 
+/// 
+/// If the format is [ExecuteFrom::Auto] it's detected with [detect_format] 
+/// and reported through the interface. 
 /// 
 /// # Parameters 
 /// * `config` - The configuration to use. 
 /// * `interface` - The interface to use. 
 /// 
-pub fn get_src(config: &Run, interface: &impl Interface) -> Result<ProgramStack, RunErrors> {
-    match config.exe_from {
+pub fn get_src(config: &Run, interface: &impl Interface) -> Result<(BabyModel, Symbols), RunErrors> {
+    let format = match config.exe_from {
+        ExecuteFrom::Auto => {
+            let format = detect_format(&config.src, interface)
+                .map_err(RunErrors::SrcFileError)?;
+            interface.log_msg(format!("Running `{}` as {}. ", config.src.to_string_lossy(), format_name(format)));
+            format
+        },
+        v => v
+    };
+    let model = match format {
         ExecuteFrom::Asm => get_src_from_asm(&config.src, config.og_notation, interface)
-            .map_err(|e| RunErrors::SrcFileError(e)),
-        ExecuteFrom::Bin => from_bin(&config.src, interface)
-            .map_err(|e| RunErrors::SrcFileError(e)),
+            .map(BabyModel::new_with_program),
+        // Auto has been resolved above. 
+        ExecuteFrom::Auto | ExecuteFrom::Bin => from_bin(config, interface),
+        ExecuteFrom::Snp => from_snp(&config.src, interface)
+            .map(BabyModel::new_with_program),
+    }.and_then(|v| apply_overlays(v, config, interface))
+        .map_err(RunErrors::SrcFileError)?;
+    let symbols = get_symbols(&config.src, format, config.og_notation, interface)
+        .map_err(RunErrors::SrcFileError)?;
+    Ok((model, symbols))
+}
+
+/// Applies the configured initial state to a model, the poked store words, 
+/// then the accumulator, then the entry point, refetching the current instruction. 
+/// 
+/// The model is unchanged if no initial state is configured. 
+/// 
+/// # Parameters 
+/// * `model` - The model to be set up. 
+/// * `config` - The configuration containing the initial state. 
+/// 
+pub fn apply_initial_state(model: BabyModel, config: &Run) -> BabyModel {
+    if config.poke.is_empty() && config.init_acc.is_none() && config.entry.is_none() {
+        return model;
     }
+    let mut res = model;
+    config.poke.iter().for_each(|v| res.main_store[v.addr] = v.value);
+    res.accumulator = config.init_acc.unwrap_or(res.accumulator);
+    res.instruction_address = config.entry.map_or(res.instruction_address, |v| v as u16);
+    res.instruction = res.main_store[res.instruction_address as usize & 0x1F] as u16;
+    res
 }
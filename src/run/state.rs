@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use baby_emulator::core::BabyModel;
+use super::profile::Profile;
+use super::clock::Clock;
+use crate::symbols::Symbols;
+
+
+/// A bounded record of previous states of a model. 
+pub struct History {
+    /// The recorded states, oldest first, paired with the clock they were recorded at. 
+    states: VecDeque<(Clock, BabyModel)>,
+    /// The maximum number of states to hold. 
+    capacity: usize,
+}
+
+impl History {
+    /// Creates a new empty history. 
+    /// 
+    /// # Parameters 
+    /// * `capacity` - The maximum number of states to hold, 0 disables recording. 
+    /// 
+    pub fn new(capacity: usize) -> History {
+        History { states: VecDeque::new(), capacity }
+    }
+
+    /// Gets the number of recorded states. 
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Records a state, dropping the oldest state if at capacity. 
+    /// 
+    /// # Parameters 
+    /// * `clock` - The clock the state was recorded at. 
+    /// * `model` - The state to be recorded. 
+    /// 
+    pub fn push(&mut self, clock: Clock, model: &BabyModel) {
+        if self.capacity == 0 { return; }
+        if self.states.len() >= self.capacity {
+            self.states.pop_front();
+        }
+        self.states.push_back((clock, model.clone()));
+    }
+
+    /// Rewinds a number of states, discarding any newer states. 
+    /// 
+    /// Returns the state rewound to, or none if there aren't enough recorded states. 
+    /// 
+    /// # Parameters 
+    /// * `count` - The number of states to rewind. 
+    /// 
+    pub fn back(&mut self, count: usize) -> Option<(Clock, BabyModel)> {
+        if count == 0 || count > self.states.len() { return None; }
+        let index = self.states.len() - count;
+        self.states.truncate(index + 1);
+        self.states.pop_back()
+    }
+
+    /// Rewinds to the most recent state about to execute from one of the 
+    /// given addresses, discarding any newer states. 
+    /// 
+    /// Returns the state rewound to, or none if no recorded state matches, 
+    /// leaving the history untouched. 
+    /// 
+    /// # Parameters 
+    /// * `addrs` - The instruction addresses to stop at. 
+    /// 
+    pub fn back_to(&mut self, addrs: &[usize]) -> Option<(Clock, BabyModel)> {
+        let index = self.states.iter()
+            .rposition(|(_, m)| addrs.contains(&(m.instruction_address as usize)))?;
+        self.back(self.states.len() - index)
+    }
+}
+
+/// Runtime state kept alongside the model whilst running. 
+pub struct RunState {
+    /// The instructions & beats executed. 
+    pub clock: Clock,
+    /// The previous states of the model. 
+    pub history: History,
+    /// The execution profile, if profiling. 
+    pub profile: Option<Profile>,
+    /// The tag names of the program's store lines. 
+    pub symbols: Symbols,
+}
+
+impl RunState {
+    /// Creates a new state with no executed instructions. 
+    /// 
+    /// # Parameters 
+    /// * `history` - The maximum number of previous states to hold. 
+    /// * `profile` - Whether to profile execution. 
+    /// 
+    pub fn new(history: usize, profile: bool) -> RunState {
+        RunState { 
+            clock: Clock::new(), 
+            history: History::new(history),
+            profile: if profile { Some(Profile::new()) } else { None },
+            symbols: Symbols::new(),
+        }
+    }
+}
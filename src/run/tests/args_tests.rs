@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+use crate::args::{Overlay, parse_overlay};
+
+#[test]
+fn test_parse_overlay() {
+    assert_eq!(parse_overlay("data.bin"), Ok(Overlay { path: PathBuf::from("data.bin"), addr: 0 }));
+    assert_eq!(parse_overlay("data.bin@0x18"), Ok(Overlay { path: PathBuf::from("data.bin"), addr: 0x18 }));
+    assert!(parse_overlay("data.bin@32").is_err());
+}
+
+#[test]
+fn test_parse_overlay_path_with_at() {
+    assert_eq!(parse_overlay("build@2/prog.bin"), Ok(Overlay { path: PathBuf::from("build@2/prog.bin"), addr: 0 }));
+    assert_eq!(parse_overlay("build@2/prog.bin@5"), Ok(Overlay { path: PathBuf::from("build@2/prog.bin"), addr: 5 }));
+}
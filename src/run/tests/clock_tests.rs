@@ -0,0 +1,19 @@
+use std::time::Duration;
+use super::clock::{Clock, instruction_beats, SCAN_BEATS, ACTION_BEATS};
+use baby_emulator::core::instructions::BabyInstruction;
+
+
+#[test]
+fn test_instruction_beats() {
+    assert_eq!(instruction_beats(&BabyInstruction::Negate(1)), SCAN_BEATS + ACTION_BEATS);
+    assert_eq!(instruction_beats(&BabyInstruction::SkipNextIfNegative), 4);
+}
+
+#[test]
+fn test_clock_tick() {
+    let mut clock = Clock::new();
+    clock.tick(&BabyInstruction::Negate(1));
+    clock.tick(&BabyInstruction::Jump(2));
+    assert_eq!(clock, Clock { steps: 2, beats: 8 });
+    assert_eq!(clock.time(), Duration::from_micros(2880));
+}
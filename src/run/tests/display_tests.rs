@@ -0,0 +1,39 @@
+use super::display::{render_word, render_display, output_display};
+use crate::test_utils::TestApplyInterface;
+use baby_emulator::core::BabyModel;
+
+
+#[test]
+fn test_render_word() {
+    assert_eq!(render_word(0), ".".repeat(32));
+    assert_eq!(render_word(-1), "-".repeat(32));
+    assert_eq!(render_word(0b1101), format!("-.--{}", ".".repeat(28)));
+}
+
+#[test]
+fn test_render_display() {
+    let mut model = BabyModel::new();
+    model.main_store[1] = 2;
+    model.accumulator = 1;
+    model.instruction_address = 3;
+    model.instruction = 0x4000;
+    let lines = render_display(&model);
+    assert_eq!(lines.len(), 36);
+    assert_eq!(lines[0], format!(" 0 {}", ".".repeat(32)));
+    assert_eq!(lines[1], format!(" 1 .-{}", ".".repeat(30)));
+    assert_eq!(lines[31], format!("31 {}", ".".repeat(32)));
+    assert_eq!(lines[32], "");
+    assert_eq!(lines[33], format!("A  -{}", ".".repeat(31)));
+    assert_eq!(lines[34], format!("CI --{}", ".".repeat(30)));
+    assert_eq!(lines[35], format!("PI {}-{}", ".".repeat(14), ".".repeat(17)));
+}
+
+#[test]
+fn test_output_display() {
+    let test_int = TestApplyInterface::new_logger_test(
+        |v| assert!(v.is_empty() || v.len() == 35), 
+        |_| panic!("Displaying a model warned. "), 
+        |_| panic!("Displaying a model errored. ")
+    );
+    output_display(&BabyModel::new_example_program(), &test_int);
+}
@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+use super::errors::{RunErrors, RunError, SrcFileErrors};
+use crate::errors::{EXIT_FILE_ERROR, EXIT_EMULATION_FAULT, EXIT_STEPS_EXCEEDED, EXIT_NO_PROGRESS, EXIT_INTERRUPTED};
+use baby_emulator::core::BabyModel;
+use baby_emulator::core::errors::{BabyErrors, IterationsExceeded};
+
+
+#[test]
+fn test_exit_codes() {
+    let file_err = RunErrors::SrcFileError(SrcFileErrors::CouldntOpenFile(PathBuf::from("foo")));
+    assert_eq!(file_err.exit_code(), EXIT_FILE_ERROR);
+    assert_eq!(RunErrors::StepsExceeded(10, 0).exit_code(), EXIT_STEPS_EXCEEDED);
+    assert_eq!(RunErrors::NoProgress(10, 0).exit_code(), EXIT_NO_PROGRESS);
+    assert_eq!(RunErrors::Interrupted(10, 0).exit_code(), EXIT_INTERRUPTED);
+    let fault = BabyErrors::IterationExceeded(IterationsExceeded::new(10, BabyModel::new()));
+    assert_eq!(RunErrors::EmulationError(Box::new(fault)).exit_code(), EXIT_EMULATION_FAULT);
+}
+
+#[test]
+fn test_describe_emulation_error() {
+    let mut model = BabyModel::new();
+    model.instruction_address = 5;
+    let fault = BabyErrors::IterationExceeded(IterationsExceeded::new(10, model));
+    assert!(RunErrors::EmulationError(Box::new(fault)).describe()
+        .starts_with("Emulation fault at instruction address 0x0005 (jump instruction). "));
+}
@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use super::execution::{run_model, run_model_from, LoopDetector};
+use super::clock::Clock;
+use super::errors::RunErrors;
+use super::state::RunState;
+use super::debug::tui::render_frame;
+use super::trace::TraceStep;
+use crate::test_utils::{TestInterface, TestSucessiveInterface, default_run};
+use crate::symbols::Symbols;
+use crate::args::{Run, Registers};
+use baby_emulator::core::{BabyModel, instructions::BabyInstruction};
+
+
+fn quiet_run() -> Run {
+    Run { debug_on_err: false, output_addr: vec![], output_regs: vec![], break_addr: vec![], ..default_run() }
+}
+
+#[test]
+fn test_run_model_stops() {
+    let stack = BabyModel::new_example_program().main_store;
+    let test_int = TestInterface::new_logger_test("", "", "");
+    assert!(run_model(quiet_run(), BabyModel::new_with_program(stack), Symbols::new(), &test_int).is_ok());
+}
+
+#[test]
+fn test_run_model_stops_immediately() {
+    let stack = BabyInstruction::to_numbers(vec![BabyInstruction::Stop]);
+    let mut conf = quiet_run();
+    conf.max_steps = Some(1);
+    let test_int = TestInterface::new_logger_test("", "", "");
+    assert!(run_model(conf, BabyModel::new_with_program(stack), Symbols::new(), &test_int).is_ok());
+}
+
+#[test]
+fn test_run_model_steps_exceeded() {
+    let stack = BabyInstruction::to_numbers(vec![
+        BabyInstruction::Negate(2),
+        BabyInstruction::Jump(3),
+        BabyInstruction::AbsoluteValue(1),
+        BabyInstruction::AbsoluteValue(0),
+    ]);
+    let mut conf = quiet_run();
+    conf.max_steps = Some(5);
+    let test_int = TestInterface::new_logger_test("", "", "");
+    match run_model(conf, BabyModel::new_with_program(stack), Symbols::new(), &test_int) {
+        Err(RunErrors::StepsExceeded(5, 1)) => (),
+        _ => panic!("Step budget was not enforced. ")
+    }
+}
+
+#[test]
+fn test_run_model_no_progress() {
+    let stack = BabyInstruction::to_numbers(vec![
+        BabyInstruction::Negate(2),
+        BabyInstruction::Jump(3),
+        BabyInstruction::AbsoluteValue(1),
+        BabyInstruction::AbsoluteValue(0),
+    ]);
+    let mut conf = quiet_run();
+    conf.detect_loops = true;
+    conf.max_steps = Some(1000);
+    let test_int = TestInterface::new_logger_test("", "", "");
+    match run_model(conf, BabyModel::new_with_program(stack), Symbols::new(), &test_int) {
+        Err(RunErrors::NoProgress(_, _)) => (),
+        _ => panic!("Repeated state was not detected. ")
+    }
+}
+
+#[test]
+fn test_loop_detector() {
+    let model = BabyModel::new();
+    let mut other = BabyModel::new();
+    other.accumulator = 5;
+    let mut detector = LoopDetector::new();
+    assert!(!detector.check(&model));
+    assert!(!detector.check(&other));
+    assert!(!detector.check(&other));
+    assert!(detector.check(&other));
+    detector.reset();
+    assert!(!detector.check(&other));
+}
+
+#[test]
+fn test_run_model_realtime() {
+    let stack = BabyModel::new_example_program().main_store;
+    let mut conf = quiet_run();
+    conf.speed = Some(2.0);
+    let test_int = TestInterface::new_logger_test("Emulated time: 0.007s (5 instructions, 20 beats). ", "", "");
+    assert!(run_model(conf, BabyModel::new_with_program(stack), Symbols::new(), &test_int).is_ok());
+}
+
+#[test]
+fn test_run_model_from_clock() {
+    let stack = BabyModel::new_example_program().main_store;
+    let mut conf = quiet_run();
+    conf.max_steps = Some(5);
+    let test_int = TestInterface::new_logger_test("", "", "");
+    let clock = Clock { steps: 5, beats: 20 };
+    match run_model_from(conf, BabyModel::new_with_program(stack), Symbols::new(), clock, &test_int) {
+        Err(RunErrors::StepsExceeded(5, 0)) => (),
+        _ => panic!("Steps executed before resuming weren't counted. ")
+    }
+}
+
+#[test]
+fn test_run_model_tui_interrupted() {
+    let mut conf = quiet_run();
+    conf.tui = true;
+    let model = BabyModel::new();
+    let stepped = model.execute().unwrap();
+    let mut state = RunState::new(0, false);
+    let first = render_frame(&model, &conf, &state);
+    state.clock = Clock { steps: 1, beats: 4 };
+    let second = render_frame(&stepped, &conf, &state);
+
+    let mut should_log: Vec<&str> = first.iter().map(|v| v.as_str()).collect();
+    should_log.push("");
+    should_log.extend(second.iter().map(|v| v.as_str()));
+    should_log.extend(["", "Running... (Ctrl-C to break)"]);
+    should_log.extend(second.iter().map(|v| v.as_str()));
+    should_log.push("");
+    let mut test_int = TestSucessiveInterface::new_logger_test(should_log, vec![], vec![]);
+    test_int.line_return = vec![format!("c"), format!("e")];
+    test_int.interrupts = vec![false, true];
+    assert!(run_model(conf, model, Symbols::new(), &test_int).is_ok());
+    assert_eq!(test_int.return_count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_run_model_trace_interrupted() {
+    let mut conf = quiet_run();
+    conf.trace = Some(PathBuf::from("trace.txt"));
+    let model = BabyModel::new();
+    let stepped = model.execute().unwrap();
+    let trace = [(1, &model, &stepped), (2, &stepped, &stepped.execute().unwrap())].iter()
+        .map(|(i, b, a)| format!("{}\n", TraceStep::new(*i, b, a).to_text()))
+        .collect::<String>();
+    let mut test_int = TestSucessiveInterface::new_logger_test(vec![], vec![], vec![]);
+    test_int.interrupts = vec![false, true];
+    test_int.should_write_addr = vec![PathBuf::from("trace.txt")];
+    test_int.should_write_data = vec![trace.into_bytes()];
+    match run_model(conf, model, Symbols::new(), &test_int) {
+        Err(RunErrors::Interrupted(2, _)) => (),
+        _ => panic!("Interrupting a traced run didn't end it. ")
+    }
+    assert_eq!(test_int.write_count.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_run_model_counts_final_stop() {
+    let stack = BabyInstruction::to_numbers(vec![BabyInstruction::Negate(0), BabyInstruction::Stop]);
+    let model = BabyModel::new_with_program(stack);
+    let stepped = model.execute().unwrap();
+    let trace = format!("{}\n{}\n", TraceStep::new(1, &model, &stepped).to_text(), TraceStep::new(2, &stepped, &stepped).to_text());
+    let mut conf = quiet_run();
+    conf.trace = Some(PathBuf::from("trace.txt"));
+    conf.output_regs = vec![Registers::Beats];
+    let mut test_int = TestSucessiveInterface::new_logger_test(vec!["8 beats"], vec![], vec![]);
+    test_int.should_write_addr = vec![PathBuf::from("trace.txt")];
+    test_int.should_write_data = vec![trace.into_bytes()];
+    assert!(run_model(conf, model, Symbols::new(), &test_int).is_ok());
+    assert_eq!(test_int.write_count.load(Ordering::Relaxed), 1);
+}
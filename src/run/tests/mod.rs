@@ -5,4 +5,23 @@ pub use super::*;
 mod output_tests;
 #[cfg(test)]
 mod srcfile_tests;
-
+#[cfg(test)]
+mod execution_tests;
+#[cfg(test)]
+mod errors_tests;
+#[cfg(test)]
+mod trace_tests;
+#[cfg(test)]
+mod state_tests;
+#[cfg(test)]
+mod snapshot_tests;
+#[cfg(test)]
+mod profile_tests;
+#[cfg(test)]
+mod realtime_tests;
+#[cfg(test)]
+mod clock_tests;
+#[cfg(test)]
+mod display_tests;
+#[cfg(test)]
+mod args_tests;
@@ -1,6 +1,9 @@
 use super::output;
 use crate::test_utils::{TestInterface, TestSucessiveInterface};
 use crate::args::Registers;
+use super::clock::Clock;
+use super::state::RunState;
+use crate::symbols::Symbols;
 use baby_emulator::core::{BabyModel, MEMORY_WORDS, instructions::BabyInstruction};
 
 
@@ -8,33 +11,38 @@ use baby_emulator::core::{BabyModel, MEMORY_WORDS, instructions::BabyInstruction
 fn test_output_model_all() {
     let model = BabyModel::new();
     let test_int = TestInterface::new_logger_test(model.core_dump().as_str(), "", "");
-    output::output_model(&vec![], &vec![], true, &model, &test_int);
+    output::output_model(&[], &[], true, &model, &RunState::new(0, false), &test_int);
 }
 
 #[test]
 fn test_output_model_addresses() {
     let model = BabyModel::new();
     let test_int = TestInterface::new_logger_test(
-        format!("{:#04x}: {:#010x}", 0, model.main_store[0 & 0x1F]).as_str(), "", ""
+        format!("{:#04x}: {:#010x}", 0, model.main_store[0]).as_str(), "", ""
     );
-    output::output_model(&vec![], &vec![0], false, &model, &test_int);
+    output::output_model(&[], &[0], false, &model, &RunState::new(0, false), &test_int);
 }
 
 #[test]
 fn test_output_register() {
     let model = BabyModel::new();
     let test_int = TestInterface::new_logger_test(format!("{:#010x}", model.accumulator).as_str(), "", "");
-    output::output_register(&Registers::Accumulator, &model, &test_int);
+    output::output_register(&Registers::Accumulator, &model, &RunState::new(0, false), &test_int);
     
     let test_int = TestInterface::new_logger_test(format!("{:#010x}", model.instruction_address).as_str(), "", "");
-    output::output_register(&Registers::InstructionAddress, &model, &test_int);
+    output::output_register(&Registers::InstructionAddress, &model, &RunState::new(0, false), &test_int);
     
     let test_int = TestInterface::new_logger_test(format!(
         "{:#010x} ({})", 
         model.instruction, 
         BabyInstruction::from_number(model.instruction).get_instr_description()
     ).as_str(), "", "");
-    output::output_register(&Registers::Instruction, &model, &test_int);
+    output::output_register(&Registers::Instruction, &model, &RunState::new(0, false), &test_int);
+
+    let mut state = RunState::new(0, false);
+    state.clock = Clock { steps: 3, beats: 12 };
+    let test_int = TestInterface::new_logger_test("12 beats", "", "");
+    output::output_register(&Registers::Beats, &model, &state, &test_int);
 }
 
 #[test]
@@ -48,12 +56,13 @@ fn test_output_all_registers() {
                 model.instruction, 
                 BabyInstruction::from_number(model.instruction).get_instr_description()
             ).as_str(),
-            format!("{:#010x}", model.instruction_address).as_str()
+            format!("{:#010x}", model.instruction_address).as_str(),
+            "0 beats",
         ], 
         vec![], 
         vec![]
     );
-    output::output_all_registers(&model, &test_int);
+    output::output_all_registers(&model, &RunState::new(0, false), &test_int);
 }
 
 #[test]
@@ -65,5 +74,19 @@ fn test_output_all_memory() {
         should_log.push(format!("{:#04x}: {:#010x}", v, model.main_store[v & 0x1F]));
     }
     test_int.should_log = should_log;
-    output::output_all_memory(&model, &test_int);
+    output::output_all_memory(&model, &Symbols::new(), &test_int);
+}
+
+#[test]
+fn test_output_labels() {
+    let mut model = BabyModel::new();
+    model.instruction_address = 5;
+    let mut state = RunState::new(0, false);
+    state.symbols = Symbols::parse("0x05 loop\n").ok().unwrap();
+
+    let test_int = TestInterface::new_logger_test("0x00000005 (loop)", "", "");
+    output::output_register(&Registers::InstructionAddress, &model, &state, &test_int);
+
+    let test_int = TestSucessiveInterface::new_logger_test(vec!["0x05: 0x00000000 (loop)", "0x06: 0x00000000"], vec![], vec![]);
+    output::output_model(&[], &[5, 6], false, &model, &state, &test_int);
 }
@@ -0,0 +1,86 @@
+use super::profile::{Profile, output_profile};
+use crate::test_utils::TestSucessiveInterface;
+use baby_emulator::core::{BabyModel, instructions::BabyInstruction};
+
+
+fn run_example() -> Profile {
+    let mut profile = Profile::new();
+    let mut model = BabyModel::new_example_program();
+    while let Ok(m) = model.execute() {
+        profile.record(&model, &m);
+        model = m;
+    }
+    profile
+}
+
+#[test]
+fn test_profile_record() {
+    let profile = run_example();
+    assert_eq!(profile.executed[0..5], [1, 1, 1, 1, 0]);
+    assert_eq!(profile.reads[5], 2);
+    assert_eq!(profile.reads[6], 2);
+    assert_eq!(profile.writes[0], 1);
+    assert_eq!(profile.instructions, [0, 0, 2, 1, 1, 0, 0]);
+}
+
+#[test]
+fn test_profile_store_write() {
+    let mut model = BabyModel::new_with_program(BabyInstruction::to_numbers(vec![
+        BabyInstruction::Store(1),
+        BabyInstruction::AbsoluteValue(3),
+    ]));
+    model.accumulator = 7;
+    let mut profile = Profile::new();
+    profile.record(&model, &model.execute().unwrap_or(model.clone()));
+    assert_eq!(profile.writes[3], 1);
+    assert_eq!(profile.reads[1], 1);
+}
+
+#[test]
+fn test_profile_same_value_store_write() {
+    let model = BabyModel::new_with_program(BabyInstruction::to_numbers(vec![
+        BabyInstruction::Store(1),
+        BabyInstruction::AbsoluteValue(3),
+    ]));
+    let mut profile = Profile::new();
+    profile.record(&model, &model.execute().unwrap_or(model.clone()));
+    assert_eq!(profile.writes[3], 1);
+}
+
+#[test]
+fn test_profile_record_stop() {
+    let mut model = BabyModel::new_example_program();
+    model.instruction_address = 4;
+    model.instruction = model.main_store[4] as u16;
+    let mut profile = Profile::new();
+    profile.record(&model, &model);
+    assert_eq!(profile.executed[4], 1);
+    assert_eq!(profile.instructions, [0, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn test_output_profile() {
+    let profile = run_example();
+    let test_int = TestSucessiveInterface::new_logger_test(
+        vec![
+            "Address    Executed      Reads     Writes",
+            "0x00              1          0          1",
+            "0x01              1          0          0",
+            "0x02              1          0          0",
+            "0x03              1          0          0",
+            "0x05              0          2          0",
+            "0x06              0          2          0",
+            "Instr         Count      Share",
+            "JMP               0       0.0%",
+            "JRP               0       0.0%",
+            "LDN               2      50.0%",
+            "STO               1      25.0%",
+            "SUB               1      25.0%",
+            "CMP               0       0.0%",
+            "STP               0       0.0%",
+        ], 
+        vec![], 
+        vec![]
+    );
+    output_profile(&profile, &test_int);
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+use super::realtime::{Pacer, format_duration, output_emulated_time};
+use super::clock::Clock;
+use crate::test_utils::TestInterface;
+
+
+#[test]
+fn test_pacer_beat() {
+    assert_eq!(Pacer::new(1.0).beat, Duration::from_micros(360));
+    assert_eq!(Pacer::new(2.0).beat, Duration::from_micros(180));
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_millis(1500)), "1.500s");
+    assert_eq!(format_duration(Duration::from_secs(52 * 60)), "52m 0.000s");
+    assert_eq!(format_duration(Duration::from_secs(3723)), "1h 2m 3.000s");
+}
+
+#[test]
+fn test_output_emulated_time() {
+    let clock = Clock { steps: 2_000_000, beats: 8_000_000 };
+    let test_int = TestInterface::new_logger_test("Emulated time: 48m 0.000s (2000000 instructions, 8000000 beats). ", "", "");
+    output_emulated_time(&clock, &test_int);
+}
@@ -0,0 +1,90 @@
+use super::snapshot::{Snapshot, SNAPSHOT_HEADER};
+use super::clock::Clock;
+use super::resume;
+use std::path::PathBuf;
+use clap::Parser;
+use crate::args::{Cli, Commands, Run, Registers};
+use crate::symbols::Symbols;
+use crate::test_utils::{TestInterface, default_run};
+use baby_emulator::core::BabyModel;
+
+
+fn snapshot_run() -> Run {
+    Run { output_regs: vec![Registers::Accumulator, Registers::InstructionAddress], break_addr: vec![6, 7], ..default_run() }
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let mut model = BabyModel::new_example_program();
+    model.accumulator = -5;
+    model.instruction_address = 3;
+    let clock = Clock { steps: 7, beats: 28 };
+    let symbols = Symbols::parse("0x03 start\n0x1f end\n").ok().unwrap();
+    let snapshot = Snapshot::new(&model, &clock, &symbols, &snapshot_run());
+    let text = snapshot.serialise();
+    assert!(text.starts_with(SNAPSHOT_HEADER));
+    assert!(text.contains("accumulator: 0xfffffffb\n"));
+    assert!(text.contains("steps: 7\nbeats: 28\n"));
+    assert!(text.contains("debug-regs: accumulator, instructionaddress\n"));
+    assert!(text.contains("symbol: 0x03 start\nsymbol: 0x1f end\n"));
+    assert_eq!(Snapshot::parse(&text), Ok(snapshot));
+}
+
+#[test]
+fn test_snapshot_parse_empty_config() {
+    let mut conf = snapshot_run();
+    conf.break_addr = vec![];
+    conf.output_addr = vec![];
+    conf.output_regs = vec![];
+    let snapshot = Snapshot::new(&BabyModel::new(), &Clock::new(), &Symbols::new(), &conf);
+    assert_eq!(Snapshot::parse(&snapshot.serialise()), Ok(snapshot));
+}
+
+#[test]
+fn test_snapshot_parse_err() {
+    assert_eq!(Snapshot::parse("foo"), Err(format!("Missing header `{}`. ", SNAPSHOT_HEADER)));
+    let text = format!("{}\nfoo: 1\n", SNAPSHOT_HEADER);
+    assert_eq!(Snapshot::parse(&text), Err("Unknown key `foo`. ".to_string()));
+    let text = format!("{}\nsteps: -1\n", SNAPSHOT_HEADER);
+    assert_eq!(Snapshot::parse(&text), Err("Invalid count `-1`. ".to_string()));
+    let text = Snapshot::new(&BabyModel::new(), &Clock::new(), &Symbols::new(), &default_run()).serialise() + "symbol: start\n";
+    assert!(Snapshot::parse(&text).err().unwrap().starts_with("Invalid symbols. "));
+    let text = format!("{}\naccumulator: 0x0\n", SNAPSHOT_HEADER);
+    assert_eq!(Snapshot::parse(&text), Err("Missing store address `0x00`, expected 32 store words. ".to_string()));
+
+    let text = Snapshot::new(&BabyModel::new(), &Clock::new(), &Symbols::new(), &default_run()).serialise();
+    let bad = text.replace("output-model: false", "output-model: yes");
+    assert_eq!(Snapshot::parse(&bad), Err("Invalid boolean `yes`, expected `true` or `false`. ".to_string()));
+    let bad = text.replace("0x07: 0x00000000", "0x06: 0x00000001");
+    assert_eq!(Snapshot::parse(&bad), Err("Duplicate store address `0x06`. ".to_string()));
+    let bad = text.replace("0x07: 0x00000000\n", "");
+    assert_eq!(Snapshot::parse(&bad), Err("Missing store address `0x07`, expected 32 store words. ".to_string()));
+}
+
+#[test]
+fn test_snapshot_apply() {
+    let snapshot = Snapshot::new(&BabyModel::new(), &Clock::new(), &Symbols::new(), &snapshot_run());
+    let mut conf = snapshot_run();
+    conf.break_addr = vec![1, 6];
+    conf.output_regs = vec![];
+    let conf = snapshot.apply(&conf);
+    assert_eq!(conf.break_addr, vec![1, 6, 7]);
+    assert_eq!(conf.output_addr, vec![5]);
+    assert_eq!(conf.output_regs, vec![Registers::Accumulator, Registers::InstructionAddress]);
+}
+
+#[test]
+fn test_resume_symbols() {
+    let mut model = BabyModel::new();
+    model.instruction = 0xE000;
+    let symbols = Symbols::parse("0x05 total\n").ok().unwrap();
+    let conf = Run { output_regs: vec![], break_addr: vec![], ..default_run() };
+    let mut test_int = TestInterface::new_logger_test("0x05: 0x00000000 (total)", "", "");
+    let text = Snapshot::new(&model, &Clock::new(), &symbols, &conf).serialise();
+    test_int.string_files.insert(PathBuf::from("snap.txt"), text);
+    let args = match Cli::parse_from(["ssemu", "resume", "snap.txt"]).command {
+        Commands::Resume(v) => v,
+        _ => panic!("Resume arguments weren't parsed as a resume command. ")
+    };
+    assert!(resume(args, &test_int).is_ok());
+}
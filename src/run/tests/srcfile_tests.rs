@@ -1,4 +1,14 @@
+use std::path::PathBuf;
+use baby_emulator::core::MEMORY_WORDS;
 use super::srcfile::*;
+use super::errors::RunError;
+use crate::args::{ExecuteFrom, Endianness, Overlay, parse_poke, parse_word};
+use baby_emulator::core::BabyModel;
+use crate::symbols::Symbols;
+use crate::snp::{parse_snp, serialise_snp};
+use std::collections::HashMap;
+use crate::test_utils::{TestInterface, TestSucessiveInterface, default_run};
+use crate::image::{Image, IMAGE_MAGIC, MAX_NAME_LEN, crc32};
 
 
 #[test]
@@ -9,3 +19,200 @@ fn test_decode_word() {
     assert_eq!((func)(0), 0x0F0F_F0F0);
     assert_eq!((func)(1), 0x0F0F_FFF0);
 }
+
+#[test]
+fn test_get_symbols() {
+    let bin = PathBuf::from("foo.bin");
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    assert_eq!(get_symbols(&bin, ExecuteFrom::Bin, false, &test_int).ok(), Some(Symbols::new()));
+
+    test_int.string_files.insert(PathBuf::from("foo.sym"), "0x03 loop\n".to_string());
+    assert_eq!(get_symbols(&bin, ExecuteFrom::Bin, false, &test_int).ok().unwrap().address_of("loop"), Some(3));
+
+    test_int.string_files.insert(PathBuf::from("foo.sym"), "loop\n".to_string());
+    match get_symbols(&bin, ExecuteFrom::Bin, false, &test_int) {
+        Err(e) => assert_eq!(e.exit_code(), crate::errors::EXIT_INVALID_IMAGE),
+        Ok(_) => panic!("Malformed symbol file was accepted. ")
+    }
+
+    let asm = PathBuf::from("foo.asm");
+    test_int.string_files.insert(asm.clone(), "stp\n:end\nabs 0d1".to_string());
+    assert_eq!(get_symbols(&asm, ExecuteFrom::Asm, false, &test_int).ok().unwrap().address_of("end"), Some(1));
+}
+
+#[test]
+fn test_parse_snp() {
+    let stack = parse_snp("32\n; comment\n0000: 00000000000001110000000000000000 ; STP\n0002: 01111111111111111111111111111111\n10000000000000000000000000000000\n").ok().unwrap();
+    assert_eq!(stack[0], 0x0000E000);
+    assert_eq!(stack[1], 0);
+    assert_eq!(stack[2], -2);
+    assert_eq!(stack[3], 1);
+    assert_eq!(parse_snp(&serialise_snp(&stack)), Ok(stack));
+
+    assert!(parse_snp("0000: 0101").is_err());
+    assert!(parse_snp("0032: 00000000000000000000000000000000").is_err());
+    assert!(parse_snp("0000: 0000000000000000000000000000000x").is_err());
+    assert!(parse_snp("foo\n").is_err());
+}
+
+#[test]
+fn test_get_src_snp() {
+    let mut conf = default_run();
+    conf.exe_from = ExecuteFrom::Snp;
+    conf.src = PathBuf::from("foo.snp");
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.snp"), "0001: 10000000000000000000000000000000\n".to_string());
+    assert_eq!(get_src(&conf, &test_int).ok().unwrap().0.main_store[1], 1);
+
+    test_int.string_files.insert(PathBuf::from("foo.snp"), "0001: 1\n".to_string());
+    match get_src(&conf, &test_int) {
+        Err(e) => assert_eq!(e.exit_code(), crate::errors::EXIT_INVALID_IMAGE),
+        Ok(_) => panic!("Malformed snp file was accepted. ")
+    }
+}
+
+#[test]
+fn test_detect_format() {
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    assert!(detect_format(&PathBuf::from("foo.ASM"), &test_int).ok() == Some(ExecuteFrom::Asm));
+    assert!(detect_format(&PathBuf::from("foo.bin"), &test_int).ok() == Some(ExecuteFrom::Bin));
+    assert!(detect_format(&PathBuf::from("foo.snp"), &test_int).ok() == Some(ExecuteFrom::Snp));
+    assert!(detect_format(&PathBuf::from("foo"), &test_int).is_err());
+
+    test_int.bin_files.insert(PathBuf::from("a"), vec![0; 128]);
+    test_int.bin_files.insert(PathBuf::from("b"), "32\n0000: 00000000000001110000000000000000\n".to_string().into_bytes());
+    test_int.bin_files.insert(PathBuf::from("c"), "stp\n".to_string().into_bytes());
+    test_int.bin_files.insert(PathBuf::from("d"), vec![0xFF, 0xFE, 0x20, 0x20]);
+    assert!(detect_format(&PathBuf::from("a"), &test_int).ok() == Some(ExecuteFrom::Bin));
+    assert!(detect_format(&PathBuf::from("b"), &test_int).ok() == Some(ExecuteFrom::Snp));
+    assert!(detect_format(&PathBuf::from("c"), &test_int).ok() == Some(ExecuteFrom::Asm));
+    assert!(detect_format(&PathBuf::from("d"), &test_int).ok() == Some(ExecuteFrom::Bin));
+}
+
+#[test]
+fn test_get_src_auto() {
+    let mut conf = default_run();
+    conf.exe_from = ExecuteFrom::Auto;
+    conf.src = PathBuf::from("foo.asm");
+    let mut test_int = TestInterface::new_logger_test("Running `foo.asm` as asm. ", "", "");
+    test_int.string_files.insert(PathBuf::from("foo.asm"), ":start\nstp".to_string());
+    let (model, symbols) = get_src(&conf, &test_int).ok().unwrap();
+    assert_eq!(model.main_store[0], 0x0000E000);
+    assert_eq!(symbols.address_of("start"), Some(0));
+}
+
+#[test]
+fn test_load_image() {
+    let mut stack = [0; MEMORY_WORDS];
+    let raw = vec![0x0F, 0x0F, 0xF0, 0xF0, 0, 0, 0, 1];
+    assert!(load_image(&mut stack, &raw, 0, false, Endianness::Big).is_err());
+    assert!(load_image(&mut stack, &raw, 30, true, Endianness::Little).is_ok());
+    assert_eq!(stack[30], 0xF0F0_0F0F_u32 as i32);
+    assert_eq!(stack[31], 0x0100_0000);
+    assert!(load_image(&mut stack, &raw, 31, true, Endianness::Big).is_err());
+    assert!(load_image(&mut stack, &[0, 0, 0], 0, true, Endianness::Big).is_err());
+}
+
+#[test]
+fn test_get_src_partial_overlay() {
+    let mut conf = default_run();
+    conf.src = PathBuf::from("foo.bin");
+    conf.load_addr = 2;
+    conf.overlay = vec![Overlay { path: PathBuf::from("bar.bin"), addr: 3 }];
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    test_int.bin_files.insert(PathBuf::from("foo.bin"), vec![0, 0, 0, 1, 0, 0, 0, 2]);
+    test_int.bin_files.insert(PathBuf::from("bar.bin"), vec![0, 0, 0, 5]);
+    let (model, _) = get_src(&conf, &test_int).ok().unwrap();
+    assert_eq!(&model.main_store[0..5], &[0, 0, 1, 5, 0]);
+
+    conf.overlay = vec![Overlay { path: PathBuf::from("bar.bin"), addr: 31 }, Overlay { path: PathBuf::from("foo.bin"), addr: 31 }];
+    match get_src(&conf, &test_int) {
+        Err(e) => assert_eq!(e.exit_code(), crate::errors::EXIT_INVALID_IMAGE),
+        Ok(_) => panic!("Overlay overflowing the store was accepted. ")
+    }
+}
+
+#[test]
+fn test_image() {
+    assert_eq!(crc32(b"123456789"), 0xCBF43926);
+
+    let mut store = [0; MEMORY_WORDS];
+    store[3] = 0x0000E000;
+    let image = Image { og_notation: true, instruction_address: 3, accumulator: -2, ..Image::new("countdown", store) };
+    let raw = image.serialise();
+    assert!(raw.starts_with(IMAGE_MAGIC));
+    assert_eq!(Image::parse(&raw), Ok(image.clone()));
+    let model = image.model();
+    assert_eq!((model.instruction_address, model.instruction, model.accumulator), (3, 0xE000, -2));
+
+    let mut corrupt = raw.clone();
+    corrupt[30] ^= 1;
+    assert!(Image::parse(&corrupt).is_err());
+    assert!(Image::parse(&raw[..raw.len() - 1]).is_err());
+    let mut version = raw.clone();
+    version[5] = 2;
+    assert!(Image::parse(&version).is_err());
+    assert!(Image::parse(b"SSEM").is_err());
+}
+
+#[test]
+fn test_get_src_raw_magic() {
+    let mut conf = default_run();
+    conf.src = PathBuf::from("prog");
+    conf.exe_from = ExecuteFrom::Auto;
+    let mut store = [0_i32; MEMORY_WORDS];
+    store[0] = 0x5353454D;
+    store[1] = 0x0000E000;
+    let raw: Vec<u8> = store.iter().flat_map(|v| v.to_be_bytes()).collect();
+    assert!(raw.starts_with(IMAGE_MAGIC));
+    assert!(!Image::is_image(&raw));
+    let mut test_int = TestSucessiveInterface::new_logger_test(vec!["Running `prog` as bin. "], vec![], vec![]);
+    test_int.bin_files = vec![HashMap::from([(PathBuf::from("prog"), raw)]); 2];
+    test_int.string_files = vec![HashMap::new()];
+    let (model, _) = get_src(&conf, &test_int).ok().unwrap();
+    assert_eq!(model.main_store, store);
+}
+
+#[test]
+fn test_image_long_name() {
+    let name = "é".repeat(MAX_NAME_LEN / 2 + 1);
+    let image = Image::new(&name, [0; MEMORY_WORDS]);
+    assert_eq!(image.header_name().len(), MAX_NAME_LEN - 1);
+    let parsed = Image::parse(&image.serialise()).ok().unwrap();
+    assert_eq!(parsed.name, name[..MAX_NAME_LEN - 1]);
+    assert_eq!(parsed.store, image.store);
+}
+
+#[test]
+fn test_get_src_image() {
+    let mut conf = default_run();
+    conf.src = PathBuf::from("prog");
+    conf.exe_from = ExecuteFrom::Auto;
+    let image = Image { instruction_address: 4, accumulator: 9, ..Image::new("prog", [0; MEMORY_WORDS]) };
+    let mut test_int = TestSucessiveInterface::new_logger_test(
+        vec!["Running `prog` as bin. ", "Loaded image `prog` (modern notation, entry 0x04). "], vec![], vec![]
+    );
+    test_int.bin_files = vec![HashMap::from([(PathBuf::from("prog"), image.serialise())]); 2];
+    test_int.string_files = vec![HashMap::new()];
+    let (model, _) = get_src(&conf, &test_int).ok().unwrap();
+    assert_eq!(model, image.model());
+}
+
+#[test]
+fn test_apply_initial_state() {
+    let model = BabyModel::new_example_program();
+    assert_eq!(apply_initial_state(model.clone(), &default_run()), model);
+
+    let mut conf = default_run();
+    conf.poke = vec![parse_poke("0x3=0xe000").ok().unwrap(), parse_poke("5 = -0b10").ok().unwrap()];
+    conf.init_acc = Some(parse_word("-0x3").ok().unwrap());
+    conf.entry = Some(3);
+    let res = apply_initial_state(BabyModel::new(), &conf);
+    assert_eq!((res.main_store[3], res.main_store[5]), (0xE000, -2));
+    assert_eq!((res.accumulator, res.instruction_address, res.instruction), (-3, 3, 0xE000));
+
+    assert!(parse_poke("0x3").is_err());
+    assert_eq!(parse_poke("32 =1"), Err("Invalid poke address `32`, Baby memory address space is 32. ".to_string()));
+    assert!(parse_poke("1=0x1ffffffff").is_err());
+    assert_eq!(parse_word("0xffffffff"), Ok(-1));
+}
@@ -0,0 +1,56 @@
+use super::state::History;
+use super::clock::Clock;
+use baby_emulator::core::BabyModel;
+
+
+fn model_at(address: u16) -> BabyModel {
+    let mut model = BabyModel::new();
+    model.instruction_address = address;
+    model
+}
+
+fn clock_at(steps: usize) -> Clock {
+    Clock { steps, beats: steps * 4 }
+}
+
+#[test]
+fn test_history_bounded() {
+    let mut history = History::new(2);
+    history.push(clock_at(0), &model_at(0));
+    history.push(clock_at(1), &model_at(1));
+    history.push(clock_at(2), &model_at(2));
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.back(2), Some((clock_at(1), model_at(1))));
+    assert_eq!(history.len(), 0);
+}
+
+#[test]
+fn test_history_disabled() {
+    let mut history = History::new(0);
+    history.push(clock_at(0), &model_at(0));
+    assert_eq!(history.len(), 0);
+    assert_eq!(history.back(1), None);
+}
+
+#[test]
+fn test_history_back() {
+    let mut history = History::new(10);
+    for i in 0..5 { history.push(clock_at(i), &model_at(i as u16)); }
+    assert_eq!(history.back(0), None);
+    assert_eq!(history.back(6), None);
+    assert_eq!(history.len(), 5);
+    assert_eq!(history.back(1), Some((clock_at(4), model_at(4))));
+    assert_eq!(history.back(2), Some((clock_at(2), model_at(2))));
+    assert_eq!(history.len(), 2);
+}
+
+#[test]
+fn test_history_back_to() {
+    let mut history = History::new(10);
+    for i in 0..6 { history.push(clock_at(i), &model_at((i % 3) as u16)); }
+    assert_eq!(history.back_to(&[7]), None);
+    assert_eq!(history.len(), 6);
+    assert_eq!(history.back_to(&[1]), Some((clock_at(4), model_at(1))));
+    assert_eq!(history.back_to(&[1]), Some((clock_at(1), model_at(1))));
+    assert_eq!(history.len(), 1);
+}
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+use super::trace::{TraceStep, Tracer, TRACE_CHUNK};
+use crate::args::TraceFormat;
+use crate::test_utils::{TestInterface, TestSucessiveInterface};
+use baby_emulator::core::{BabyModel, instructions::BabyInstruction};
+
+
+#[test]
+fn test_trace_step_text() {
+    let before = BabyModel::new_example_program();
+    let after = before.execute().unwrap_or(before.clone());
+    let record = TraceStep::new(1, &before, &after);
+    assert_eq!(record.instruction, BabyInstruction::Negate(5));
+    assert_eq!(
+        record.to_text(), 
+        "       1 0x00: LDN 0x05 = 0xfffffffb  acc 0x00000000 -> 0x00000005"
+    );
+}
+
+#[test]
+fn test_trace_step_store_write() {
+    let mut before = BabyModel::new_with_program(BabyInstruction::to_numbers(vec![
+        BabyInstruction::Store(1),
+        BabyInstruction::AbsoluteValue(3),
+    ]));
+    before.accumulator = 7;
+    let after = before.execute().unwrap_or(before.clone());
+    let record = TraceStep::new(4, &before, &after);
+    assert_eq!(record.store_write, Some((3, 7)));
+    assert_eq!(
+        record.to_json(), 
+        "{\"step\":4,\"address\":0,\"instruction\":\"STO\",\"operand_address\":1,\"operand\":3,\"acc_before\":7,\"acc_after\":7,\"store_write\":{\"address\":3,\"value\":7}}"
+    );
+}
+
+#[test]
+fn test_trace_step_same_value_store_write() {
+    let before = BabyModel::new_with_program(BabyInstruction::to_numbers(vec![
+        BabyInstruction::Store(1),
+        BabyInstruction::AbsoluteValue(3),
+    ]));
+    let after = before.execute().unwrap_or(before.clone());
+    assert_eq!(before.main_store, after.main_store);
+    assert_eq!(TraceStep::new(1, &before, &after).store_write, Some((3, 0)));
+}
+
+#[test]
+fn test_tracer_write() {
+    let before = BabyModel::new_example_program();
+    let after = before.execute().unwrap_or(before.clone());
+    let mut tracer = Tracer::new(TraceFormat::Json, PathBuf::from("trace.jsonl"));
+    let mut test_int = TestInterface::new_logger_test("", "", "");
+    assert!(tracer.record(1, &before, &after, &test_int).is_ok());
+    test_int.should_write_addr = PathBuf::from("trace.jsonl");
+    test_int.should_write_data = format!("{}\n", TraceStep::new(1, &before, &after).to_json()).into_bytes();
+    assert!(tracer.flush(&test_int).is_ok());
+    assert!(tracer.flush(&test_int).is_ok());
+}
+
+#[test]
+fn test_tracer_chunks() {
+    let before = BabyModel::new_example_program();
+    let after = before.execute().unwrap_or(before.clone());
+    let line = |i: usize| format!("{}\n", TraceStep::new(i, &before, &after).to_text());
+    let mut tracer = Tracer::new(TraceFormat::Text, PathBuf::from("trace.txt"));
+    let mut test_int = TestSucessiveInterface::new_logger_test(vec![], vec![], vec![]);
+    test_int.should_write_addr = vec![PathBuf::from("trace.txt"), PathBuf::from("trace.txt")];
+    test_int.should_write_data = vec![
+        (1..=TRACE_CHUNK).map(&line).collect::<String>().into_bytes(),
+        line(TRACE_CHUNK + 1).into_bytes(),
+    ];
+    for i in 1..=TRACE_CHUNK + 1 {
+        assert!(tracer.record(i, &before, &after, &test_int).is_ok());
+    }
+    assert_eq!(test_int.write_count.load(std::sync::atomic::Ordering::Relaxed), 1);
+    assert!(tracer.flush(&test_int).is_ok());
+    assert_eq!(test_int.write_count.load(std::sync::atomic::Ordering::Relaxed), 2);
+}
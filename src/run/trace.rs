@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+use baby_emulator::core::{BabyModel, instructions::BabyInstruction};
+use crate::args::TraceFormat;
+use crate::interface::Interface;
+use crate::notation::{mnemonic, has_operand};
+use super::errors::RunErrors;
+
+
+/// The number of records kept before they're appended to the trace file, the 
+/// rest are written when the run ends, including when it's interrupted (Ctrl-C). 
+pub const TRACE_CHUNK: usize = 256;
+
+/// The record of a single executed instruction. 
+pub struct TraceStep {
+    /// The number of the step, starting at 1. 
+    pub step: usize,
+    /// The address of the executed instruction. 
+    pub address: u16,
+    /// The decoded executed instruction. 
+    pub instruction: BabyInstruction,
+    /// The value at the instruction's operand address. 
+    pub operand: i32,
+    /// The accumulator before execution. 
+    pub acc_before: i32,
+    /// The accumulator after execution. 
+    pub acc_after: i32,
+    /// A word written to the store by the instruction (address, value). 
+    pub store_write: Option<(usize, i32)>,
+}
+
+impl TraceStep {
+    /// Builds a trace record from the model before and after a step, a store 
+    /// instruction writes the accumulator to the address held at its operand, 
+    /// even if the value is already there. 
+    /// 
+    /// # Parameters 
+    /// * `step` - The number of the step. 
+    /// * `before` - The model before the instruction was executed. 
+    /// * `after` - The model after the instruction was executed. 
+    /// 
+    pub fn new(step: usize, before: &BabyModel, after: &BabyModel) -> TraceStep {
+        let instruction = BabyInstruction::from_number(before.instruction);
+        let store_write = match instruction {
+            BabyInstruction::Store(p) => Some((before.main_store[p as usize] as usize & 0x1F, before.accumulator)),
+            _ => None,
+        };
+        TraceStep {
+            step,
+            address: before.instruction_address,
+            instruction,
+            operand: before.main_store[instruction.get_operand()],
+            acc_before: before.accumulator,
+            acc_after: after.accumulator,
+            store_write,
+        }
+    }
+
+    /// Formats the record as a human readable line. 
+    pub fn to_text(&self) -> String {
+        let operand = if has_operand(&self.instruction) {
+            format!("{:#04x} = {:#010x}", self.instruction.get_operand(), self.operand)
+        } else { String::new() };
+        let mut res = format!(
+            "{:>8} {:#04x}: {} {:<18} acc {:#010x} -> {:#010x}", 
+            self.step, 
+            self.address, 
+            mnemonic(&self.instruction),
+            operand,
+            self.acc_before,
+            self.acc_after
+        );
+        if let Some((addr, value)) = self.store_write {
+            res += &format!(" store {:#04x} <- {:#010x}", addr, value);
+        }
+        res
+    }
+
+    /// Formats the record as a single line JSON object. 
+    pub fn to_json(&self) -> String {
+        let store_write = match self.store_write {
+            Some((addr, value)) => format!("{{\"address\":{},\"value\":{}}}", addr, value),
+            None => "null".to_string()
+        };
+        format!(
+            "{{\"step\":{},\"address\":{},\"instruction\":\"{}\",\"operand_address\":{},\"operand\":{},\"acc_before\":{},\"acc_after\":{},\"store_write\":{}}}",
+            self.step,
+            self.address,
+            mnemonic(&self.instruction),
+            self.instruction.get_operand(),
+            self.operand,
+            self.acc_before,
+            self.acc_after,
+            store_write
+        )
+    }
+}
+
+/// Records executed instructions to a trace file, appending them in chunks. 
+pub struct Tracer {
+    /// The format to record in. 
+    format: TraceFormat,
+    /// The path of the trace file. 
+    out: PathBuf,
+    /// The formatted records not yet written. 
+    lines: Vec<String>,
+    /// Whether the trace file has been created. 
+    created: bool,
+}
+
+impl Tracer {
+    /// Creates a new empty tracer. 
+    /// 
+    /// # Parameters 
+    /// * `format` - The format to record in. 
+    /// * `out` - The path of the trace file. 
+    /// 
+    pub fn new(format: TraceFormat, out: PathBuf) -> Tracer {
+        Tracer { format, out, lines: vec![], created: false }
+    }
+
+    /// Records an executed instruction, writing the records kept so far once 
+    /// there's a chunk of them. 
+    /// 
+    /// # Parameters 
+    /// * `step` - The number of the step. 
+    /// * `before` - The model before the instruction was executed. 
+    /// * `after` - The model after the instruction was executed. 
+    /// * `int` - The interface to write to. 
+    /// 
+    /// # Returns 
+    /// * [Ok(())] - The instruction was recorded. 
+    /// * [Err(RunErrors)] - The trace file couldn't be written. 
+    /// 
+    pub fn record(&mut self, step: usize, before: &BabyModel, after: &BabyModel, int: &impl Interface) -> Result<(), RunErrors> {
+        let record = TraceStep::new(step, before, after);
+        self.lines.push(match self.format {
+            TraceFormat::Text => record.to_text(),
+            TraceFormat::Json => record.to_json(),
+        });
+        if self.lines.len() >= TRACE_CHUNK { self.flush(int) }
+        else { Ok(()) }
+    }
+
+    /// Writes the records kept so far to the trace file, creating it the 
+    /// first time and appending to it after. 
+    /// 
+    /// # Parameters 
+    /// * `int` - The interface to write to. 
+    /// 
+    /// # Returns 
+    /// * [Ok(())] - The records were written. 
+    /// * [Err(RunErrors)] - The trace file couldn't be written. 
+    /// 
+    pub fn flush(&mut self, int: &impl Interface) -> Result<(), RunErrors> {
+        if self.created && self.lines.is_empty() { return Ok(()); }
+        let data = self.lines.drain(..)
+            .map(|l| format!("{}\n", l))
+            .collect::<String>()
+            .into_bytes();
+        let res = if self.created { int.append_fs_bytes(data, &self.out) }
+            else { int.write_fs_bytes(data, &self.out) };
+        self.created = true;
+        res.map_err(|_| RunErrors::CouldntWriteFile(self.out.clone()))
+    }
+}
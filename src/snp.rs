@@ -0,0 +1,79 @@
+use baby_emulator::core::{MEMORY_WORDS, instructions::BabyInstruction};
+use crate::assemble::listing::baby_bits;
+use crate::notation::describe_instruction;
+use crate::run::ProgramStack;
+
+
+/// Parses a store line of 32 binary digits, least significant bit first. 
+/// 
+/// # Parameters 
+/// * `bits` - The binary digits. 
+/// 
+fn parse_bits(bits: &str) -> Option<i32> {
+    if bits.len() != 32 { return None; }
+    bits.chars().enumerate().try_fold(0i32, |word, (i, c)| match c {
+        '0' => Some(word),
+        '1' => Some(word | (1 << i)),
+        _ => None,
+    })
+}
+
+/// Formats a program stack as a `.snp` file, the number of store lines 
+/// followed by every store line as `NNNN: bits`, least significant bit 
+/// first, with the decoded instruction as a comment. 
+/// 
+/// # Parameters 
+/// * `stack` - The program stack to be formatted. 
+/// 
+pub fn serialise_snp(stack: &ProgramStack) -> String {
+    let mut res = format!("{}\n", MEMORY_WORDS);
+    for (i, word) in stack.iter().enumerate() {
+        let instruction = describe_instruction(&BabyInstruction::from_number(*word as u16));
+        res += &format!("{:04}: {} ; {}\n", i, baby_bits(*word), instruction);
+    }
+    res
+}
+
+/// Parses a `.snp` file into a program stack. 
+/// 
+/// Anything after a `;` is ignored, as is a leading line holding the number of 
+/// store lines. Store lines are 32 binary digits, least significant bit first, 
+/// optionally prefixed with their decimal address `NNNN:`, lines without an 
+/// address follow on from the previous line. 
+/// 
+/// # Parameters 
+/// * `src` - The text of the `.snp` file. 
+/// 
+/// # Returns 
+/// * [Ok(ProgramStack)] - The parsed program stack. 
+/// * [Err(String)] - A message describing the malformed line. 
+/// 
+pub fn parse_snp(src: &str) -> Result<ProgramStack, String> {
+    let mut res = [0; MEMORY_WORDS];
+    let mut next = 0;
+    let mut first = true;
+    for (i, line) in src.lines().enumerate() {
+        let line = line.split(";").next().unwrap_or("").trim();
+        if line.is_empty() { continue; }
+        if std::mem::take(&mut first) && !line.contains(":") && line.len() != 32 {
+            line.parse::<usize>()
+                .map_err(|_| format!("Invalid store line count `{}` on line {}. ", line, i + 1))?;
+            continue;
+        }
+        let (addr, bits) = match line.split_once(":") {
+            Some((a, b)) => (
+                a.trim().parse::<usize>()
+                    .map_err(|_| format!("Invalid address `{}` on line {}. ", a.trim(), i + 1))?,
+                b.trim()
+            ),
+            None => (next, line),
+        };
+        if addr >= MEMORY_WORDS {
+            return Err(format!("Address {} on line {} is outside the store of {} words. ", addr, i + 1, MEMORY_WORDS));
+        }
+        res[addr] = parse_bits(bits)
+            .ok_or(format!("Line {} should be 32 binary digits, found `{}`. ", i + 1, bits))?;
+        next = addr + 1;
+    }
+    Ok(res)
+}
@@ -0,0 +1,18 @@
+; abs_value x
+; Leaves the absolute value of the value at `x` in the accumulator. 10 words.
+%macro abs_value x
+    jrp $.in
+:.tp
+    abs $.t
+:.t
+    abs 0d0
+:.in
+    abs 0d5
+:.out
+    abs 0d3
+    ldn x                   ; -x is already positive unless x is
+    cmp
+    jrp $.out
+    sto $.tp                ; otherwise load x
+    ldn $.t
+%endmacro
@@ -0,0 +1,44 @@
+; divide a, b
+; Divides the value at `a` by the value at `b`, leaving the quotient rounded
+; down in the accumulator. `a` must not be negative & `b` must be positive.
+; 28 words.
+%macro divide a, b
+    ldn a                   ; r = a
+    sto $.rp
+    ldn $.one               ; q = -1, counting the subtraction that goes negative
+    sto $.qp
+:.loop
+    ldn $.q                 ; q = q + 1, t = -q
+    sub $.one
+    sto $.tp
+    ldn $.t
+    sto $.qp
+    ldn $.r                 ; r = r - b
+    sub b
+    sto $.rp
+    ldn $.r
+    sto $.rp
+    sub $.one               ; stop once r is negative
+    cmp
+    jrp $.out
+    jmp $.loopp
+:.loopp
+    abs $.loop
+:.qp
+    abs $.q
+:.q
+    abs 0d0
+:.tp
+    abs $.t
+:.t
+    abs 0d0
+:.rp
+    abs $.r
+:.r
+    abs 0d0
+:.one
+    abs 0d1
+:.out
+    abs 0d11
+    ldn $.t
+%endmacro
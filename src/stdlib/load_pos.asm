@@ -0,0 +1,15 @@
+; load_pos x
+; Loads the value at `x` into the accumulator, rather than its negation like
+; `ldn`. 7 words.
+%macro load_pos x
+    ldn x
+    sto $.tp
+    ldn $.t
+    jrp $.out
+:.tp
+    abs $.t
+:.t
+    abs 0d0
+:.out
+    abs 0d4
+%endmacro
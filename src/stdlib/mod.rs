@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+#[cfg(test)]
+mod tests;
+
+
+/// The routines bundled into the binary, by name, each the source of a file 
+/// defining a macro of the same name, included as `%include <name>`. 
+pub const ROUTINES: [(&str, &str); 7] = [
+    ("load_pos", include_str!("load_pos.asm")),
+    ("abs_value", include_str!("abs_value.asm")),
+    ("multiply", include_str!("multiply.asm")),
+    ("divide", include_str!("divide.asm")),
+    ("modulo", include_str!("modulo.asm")),
+    ("shift_left", include_str!("shift_left.asm")),
+    ("shift_right", include_str!("shift_right.asm")),
+];
+
+/// Gets the source of a bundled routine. 
+/// 
+/// # Parameters 
+/// * `name` - The name of the routine. 
+/// 
+/// # Returns 
+/// * [Some(&str)] - The source defining the routine's macro. 
+/// * [None] - There is no routine by that name. 
+/// 
+pub fn routine(name: &str) -> Option<&'static str> {
+    ROUTINES.iter().find(|(n, _)| *n == name).map(|(_, src)| *src)
+}
+
+/// Gets the path a bundled routine is reported as being from, `<name>`. 
+/// 
+/// # Parameters 
+/// * `name` - The name of the routine. 
+/// 
+pub fn routine_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("<{}>", name))
+}
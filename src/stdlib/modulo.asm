@@ -0,0 +1,25 @@
+; modulo a, b
+; Leaves the remainder of dividing the value at `a` by the value at `b` in the
+; accumulator. `a` must not be negative & `b` must be positive. 15 words.
+%macro modulo a, b
+    ldn a                   ; r = a
+    sto $.rp
+:.loop
+    ldn $.r                 ; r - b, if negative r is the remainder
+    sub b
+    cmp
+    sto $.rp                ; otherwise r = r - b
+    ldn $.r
+    sto $.rp
+    cmp                     ; carry on unless r is the remainder (or 0)
+    jrp $.out
+    jmp $.loopp
+:.loopp
+    abs $.loop
+:.rp
+    abs $.r
+:.r
+    abs 0d0
+:.out
+    abs 0d6
+%endmacro
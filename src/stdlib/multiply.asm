@@ -0,0 +1,43 @@
+; multiply a, b
+; Multiplies the values at `a` & `b`, leaving the product in the accumulator.
+; `b` must not be negative. 28 words.
+%macro multiply a, b
+    ldn a                   ; p = -a, b + 1 additions of `a` follow
+    sto $.pp
+    ldn b                   ; r = b
+    sto $.rp
+:.loop
+    ldn $.p                 ; p = p + a, t = -p
+    sub a
+    sto $.tp
+    ldn $.t
+    sto $.pp
+    ldn $.r                 ; r = r - 1
+    sub $.one
+    sto $.rp
+    ldn $.r
+    sto $.rp
+    sub $.one               ; stop once r is negative
+    cmp
+    jrp $.out
+    jmp $.loopp
+:.loopp
+    abs $.loop
+:.pp
+    abs $.p
+:.p
+    abs 0d0
+:.tp
+    abs $.t
+:.t
+    abs 0d0
+:.rp
+    abs $.r
+:.r
+    abs 0d0
+:.one
+    abs 0d1
+:.out
+    abs 0d11
+    ldn $.t
+%endmacro
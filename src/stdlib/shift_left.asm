@@ -0,0 +1,40 @@
+; shift_left x, n
+; Shifts the value at `x` left by the value at `n` places, doubling it by
+; adding it to itself, leaving the result in the accumulator. `n` must not be
+; negative. 26 words.
+%macro shift_left x, n
+    ldn x                   ; t = -x
+    sto $.tp
+    ldn n                   ; r = n
+    sto $.rp
+:.loop
+    ldn $.r                 ; r = r - 1
+    sub $.one
+    sto $.rp
+    ldn $.r
+    sto $.rp
+    sub $.one               ; stop once r is negative
+    cmp
+    jrp $.out
+    ldn $.t                 ; t = t + t
+    sub $.t
+    sto $.tp
+    ldn $.t
+    sto $.tp
+    jmp $.loopp
+:.loopp
+    abs $.loop
+:.tp
+    abs $.t
+:.t
+    abs 0d0
+:.rp
+    abs $.r
+:.r
+    abs 0d0
+:.one
+    abs 0d1
+:.out
+    abs 0d14
+    ldn $.t
+%endmacro
@@ -0,0 +1,38 @@
+; shift_right x
+; Shifts the value at `x` right by one place, leaving the result in the
+; accumulator, by counting up to the largest value that added to itself
+; doesn't exceed `x`. `x` must not be negative. 24 words.
+%macro shift_right x
+    ldn x                   ; h = 0
+    sto $.tp
+    sub $.t
+    sto $.hp
+:.loop
+    ldn x                   ; stop unless h + 1 + h + 1 <= x
+    sub $.h
+    sub $.h
+    sub $.negone
+    cmp
+    jrp $.out
+    ldn $.h                 ; h = h + 1
+    sub $.negone
+    sto $.tp
+    ldn $.t
+    sto $.hp
+    jmp $.loopp
+:.loopp
+    abs $.loop
+:.hp
+    abs $.h
+:.h
+    abs 0d0
+:.tp
+    abs $.t
+:.t
+    abs 0d0
+:.negone
+    abs 0d-1
+:.out
+    abs 0d14
+    ldn $.h
+%endmacro
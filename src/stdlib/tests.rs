@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+use super::*;
+use baby_emulator::core::{BabyModel, instructions::BabyInstruction};
+use crate::args::{Run, Registers};
+use crate::assemble::preprocess::preprocess;
+use crate::assemble::diagnostics::assemble_source;
+use crate::run::execution::run_model;
+use crate::symbols::Symbols;
+use crate::test_utils::{TestInterface, default_run};
+
+
+/// Includes a routine, invokes it on `$a` & `$b` then stops, asserting the 
+/// accumulator holds the expected value. 
+fn run_routine(name: &str, invocation: &str, a: i32, b: i32, expected: i32) {
+    let asm = format!("%include <{}>\n{}\nstp\n:a\nabs 0d{}\n:b\nabs 0d{}", name, invocation, a, b);
+    let test_int = TestInterface::new_logger_test("", "", "");
+    let pre = preprocess(&PathBuf::from("foo.asm"), &asm, &test_int).unwrap();
+    let stack = BabyInstruction::to_numbers(assemble_source(&pre, false).unwrap());
+    let test_int = TestInterface::new_logger_test(&format!("{:#010x}", expected), "", "");
+    let conf = Run {
+        debug_on_err: false,
+        output_addr: vec![],
+        output_regs: vec![Registers::Accumulator],
+        break_addr: vec![],
+        max_steps: Some(10000),
+        ..default_run()
+    };
+    assert!(run_model(conf, BabyModel::new_with_program(stack), Symbols::new(), &test_int).is_ok(),
+        "`{}` with a = {} & b = {} didn't stop", invocation, a, b);
+}
+
+#[test]
+fn test_routine() {
+    assert!(routine("multiply").unwrap().contains("%macro multiply a, b"));
+    assert_eq!(routine("foo"), None);
+    assert_eq!(routine_path("divide"), PathBuf::from("<divide>"));
+    for (name, src) in ROUTINES {
+        assert!(src.contains(&format!("%macro {}", name)), "`{}` doesn't define its macro", name);
+    }
+}
+
+#[test]
+fn test_include_routine_errors() {
+    let test_int = TestInterface::new_logger_test("", "", "");
+    let res = preprocess(&PathBuf::from("foo.asm"), "%include <multipy>", &test_int).unwrap_err();
+    assert!(res.contains("there's no bundled routine `multipy`"));
+    assert!(res.contains("did you mean `<multiply>`?"));
+    let res = preprocess(&PathBuf::from("foo.asm"), "%include <foo>", &test_int).unwrap_err();
+    assert!(res.contains("the bundled routines are `load_pos`, `abs_value`"));
+}
+
+#[test]
+fn test_load_pos() {
+    run_routine("load_pos", "load_pos $a", 12, 0, 12);
+    run_routine("load_pos", "load_pos $a", -12, 0, -12);
+    run_routine("load_pos", "load_pos $a", 0, 0, 0);
+}
+
+#[test]
+fn test_abs_value() {
+    run_routine("abs_value", "abs_value $a", 9, 0, 9);
+    run_routine("abs_value", "abs_value $a", -9, 0, 9);
+    run_routine("abs_value", "abs_value $a", 0, 0, 0);
+}
+
+#[test]
+fn test_multiply() {
+    run_routine("multiply", "multiply $a, $b", 6, 7, 42);
+    run_routine("multiply", "multiply $a, $b", -3, 4, -12);
+    run_routine("multiply", "multiply $a, $b", 5, 1, 5);
+    run_routine("multiply", "multiply $a, $b", 5, 0, 0);
+    run_routine("multiply", "multiply $b, $a", 0, 8, 0);
+}
+
+#[test]
+fn test_divide() {
+    run_routine("divide", "divide $a, $b", 7, 2, 3);
+    run_routine("divide", "divide $a, $b", 8, 2, 4);
+    run_routine("divide", "divide $a, $b", 42, 6, 7);
+    run_routine("divide", "divide $a, $b", 2, 5, 0);
+    run_routine("divide", "divide $a, $b", 0, 3, 0);
+}
+
+#[test]
+fn test_modulo() {
+    run_routine("modulo", "modulo $a, $b", 7, 3, 1);
+    run_routine("modulo", "modulo $a, $b", 9, 3, 0);
+    run_routine("modulo", "modulo $a, $b", 2, 5, 2);
+    run_routine("modulo", "modulo $a, $b", 0, 4, 0);
+    run_routine("modulo", "modulo $a, $b", 43, 1, 0);
+}
+
+#[test]
+fn test_shift_left() {
+    run_routine("shift_left", "shift_left $a, $b", 3, 4, 48);
+    run_routine("shift_left", "shift_left $a, $b", -5, 2, -20);
+    run_routine("shift_left", "shift_left $a, $b", 3, 0, 3);
+    run_routine("shift_left", "shift_left $a, $b", 1, 30, 1 << 30);
+}
+
+#[test]
+fn test_shift_right() {
+    run_routine("shift_right", "shift_right $a", 9, 0, 4);
+    run_routine("shift_right", "shift_right $a", 8, 0, 4);
+    run_routine("shift_right", "shift_right $a", 1, 0, 0);
+    run_routine("shift_right", "shift_right $a", 0, 0, 0);
+}
+
+#[test]
+fn test_uninvoked_routines_take_no_words() {
+    let asm = "%include <multiply>\n%include <modulo>\nmodulo $a, $b\nstp\n:a\nabs 0d7\n:b\nabs 0d4".to_string();
+    let test_int = TestInterface::new_logger_test("", "", "");
+    let pre = preprocess(&PathBuf::from("foo.asm"), &asm, &test_int).unwrap();
+    assert_eq!(assemble_source(&pre, false).unwrap().len(), 18);
+}
@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+use baby_emulator::core::MEMORY_WORDS;
+use crate::assemble::listing::{map_source, SourceLine};
+
+
+/// The file extension of symbol files, written next to the assembled binary. 
+pub const SYMBOL_EXTENSION: &str = "sym";
+
+/// The tag names of store lines in an assembled program. 
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Symbols {
+    /// The tag names and the addresses they are bound to, in address order. 
+    entries: Vec<(String, usize)>,
+}
+
+impl Symbols {
+    /// Creates a new empty symbol table. 
+    pub fn new() -> Symbols {
+        Symbols { entries: vec![] }
+    }
+
+    /// Creates a symbol table from tag names and the addresses they are bound to. 
+    /// 
+    /// # Parameters 
+    /// * `entries` - The tag names and addresses. 
+    /// 
+    pub fn from_entries(mut entries: Vec<(String, usize)>) -> Symbols {
+        entries.sort_by_key(|(_, a)| *a);
+        Symbols { entries }
+    }
+
+    /// Creates a symbol table from the source lines that produced each store word. 
+    /// 
+    /// # Parameters 
+    /// * `sources` - The source lines, ordered by the address they produced. 
+    /// 
+    pub fn from_sources(sources: &[SourceLine]) -> Symbols {
+        let entries = sources.iter().enumerate()
+            .filter_map(|(i, v)| v.tag.clone().map(|t| (t, i)))
+            .collect();
+        Symbols { entries }
+    }
+
+    /// Creates a symbol table from an asm source. 
+    /// 
+    /// # Parameters 
+    /// * `asm` - The asm source. 
+    /// * `og_notation` - Whether the source uses original notation. 
+    /// 
+    pub fn from_asm(asm: &str, og_notation: bool) -> Symbols {
+        Symbols::from_sources(&map_source(asm, og_notation))
+    }
+
+    /// Determines if there are no symbols. 
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the address a tag name is bound to, ignoring case. 
+    /// 
+    /// # Parameters 
+    /// * `name` - The tag name to look up. 
+    /// 
+    pub fn address_of(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.entries.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, a)| *a)
+    }
+
+    /// Gets the first tag name bound to an address. 
+    /// 
+    /// # Parameters 
+    /// * `addr` - The address to look up. 
+    /// 
+    pub fn name_of(&self, addr: usize) -> Option<&str> {
+        self.entries.iter()
+            .find(|(_, a)| *a == addr)
+            .map(|(n, _)| n.as_str())
+    }
+
+    /// Serialises the symbol table, one `address name` pair per line. 
+    pub fn serialise(&self) -> String {
+        self.entries.iter()
+            .map(|(n, a)| format!("{:#04x} {}\n", a, n))
+            .collect()
+    }
+
+    /// Parses a symbol table serialised by [Symbols::serialise]. 
+    /// 
+    /// Blank lines and anything after a `;` are ignored. 
+    /// 
+    /// # Parameters 
+    /// * `src` - The serialised symbol table. 
+    /// 
+    /// # Returns 
+    /// * [Ok(Symbols)] - The parsed symbol table. 
+    /// * [Err(String)] - A message describing the malformed line. 
+    /// 
+    pub fn parse(src: &str) -> Result<Symbols, String> {
+        let mut entries = vec![];
+        for (i, line) in src.lines().enumerate() {
+            let line = line.split(";").next().unwrap_or("").trim();
+            if line.is_empty() { continue; }
+            let (addr, name) = line.split_once(char::is_whitespace)
+                .ok_or(format!("Line {} should be an address followed by a tag name. ", i + 1))?;
+            let addr = usize::from_str_radix(addr.trim_start_matches("0x"), 16)
+                .ok().filter(|v| *v < MEMORY_WORDS)
+                .ok_or(format!("Invalid address `{}` on line {}. ", addr, i + 1))?;
+            entries.push((name.trim().to_owned(), addr));
+        }
+        Ok(Symbols { entries })
+    }
+}
+
+/// Gets the path of the symbol file for a binary file. 
+/// 
+/// # Parameters 
+/// * `bin` - The path to the binary file. 
+/// 
+pub fn symbol_path(bin: &Path) -> PathBuf {
+    bin.with_extension(SYMBOL_EXTENSION)
+}
@@ -1,7 +1,9 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use crate::interface::Interface;
+use crate::args::{Run, TraceFormat, Endianness, ExecuteFrom, Registers};
 
 
 pub struct TestInterface {
@@ -21,7 +23,7 @@ impl TestInterface {
             should_log: should_log.to_owned(),
             should_warn: should_warn.to_owned(),
             should_err: should_err.to_owned(),
-            line_return: format!(""),
+            line_return: String::new(),
             string_files: HashMap::new(),
             bin_files: HashMap::new(),
             should_write_addr: PathBuf::new(),
@@ -46,23 +48,35 @@ impl Interface for TestInterface {
     fn get_line(&self) -> String {
         self.line_return.clone()
     }
-    fn read_fs_string(&self, path: &PathBuf) -> Result<String, ()> {
+    fn read_fs_string(&self, path: &Path) -> Result<String, ()> {
         match self.string_files.get(path) {
             Some(v) => Ok(v.clone()),
             None => Err(())
         }
     }
-    fn read_fs_bytes(&self, path: &PathBuf) -> Result<Vec<u8>, ()> {
+    fn read_fs_bytes(&self, path: &Path) -> Result<Vec<u8>, ()> {
         match self.bin_files.get(path) {
             Some(v) => Ok(v.clone()),
             None => Err(())
         }
     }
-    fn write_fs_bytes(&self, data: Vec<u8>, out: &PathBuf) -> Result<(), ()> {
+    fn write_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
         assert_eq!(self.should_write_addr, *out);
         assert_eq!(self.should_write_data, data);
         Ok(())
     }
+    fn append_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
+        self.write_fs_bytes(data, out)
+    }
+    fn sleep(&self, _duration: Duration) {
+        
+    }
+    fn watch_interrupts(&self) {
+        
+    }
+    fn interrupted(&self) -> bool {
+        false
+    }
 }
 
 pub struct TestApplyInterface {
@@ -82,7 +96,7 @@ impl TestApplyInterface {
             should_log: should_log.to_owned(),
             should_warn: should_warn.to_owned(),
             should_err: should_err.to_owned(),
-            line_return: format!(""),
+            line_return: String::new(),
             string_files: HashMap::new(),
             bin_files: HashMap::new(),
             should_write_addr: PathBuf::new(),
@@ -107,23 +121,35 @@ impl Interface for TestApplyInterface {
     fn get_line(&self) -> String {
         self.line_return.clone()
     }
-    fn read_fs_string(&self, path: &PathBuf) -> Result<String, ()> {
+    fn read_fs_string(&self, path: &Path) -> Result<String, ()> {
         match self.string_files.get(path) {
             Some(v) => Ok(v.clone()),
             None => Err(())
         }
     }
-    fn read_fs_bytes(&self, path: &PathBuf) -> Result<Vec<u8>, ()> {
+    fn read_fs_bytes(&self, path: &Path) -> Result<Vec<u8>, ()> {
         match self.bin_files.get(path) {
             Some(v) => Ok(v.clone()),
             None => Err(())
         }
     }
-    fn write_fs_bytes(&self, data: Vec<u8>, out: &PathBuf) -> Result<(), ()> {
+    fn write_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
         assert_eq!(self.should_write_addr, *out);
         assert_eq!(self.should_write_data, data);
         Ok(())
     }
+    fn append_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
+        self.write_fs_bytes(data, out)
+    }
+    fn sleep(&self, _duration: Duration) {
+        
+    }
+    fn watch_interrupts(&self) {
+        
+    }
+    fn interrupted(&self) -> bool {
+        false
+    }
 }
 
 pub struct TestSucessiveInterface {
@@ -141,7 +167,9 @@ pub struct TestSucessiveInterface {
     pub bin_files: Vec<HashMap<PathBuf, Vec<u8>>>,
     pub write_count: AtomicUsize,
     pub should_write_addr: Vec<PathBuf>,
-    pub should_write_data: Vec<Vec<u8>>
+    pub should_write_data: Vec<Vec<u8>>,
+    pub interrupt_count: AtomicUsize,
+    pub interrupts: Vec<bool>
 }
 
 impl TestSucessiveInterface {
@@ -162,6 +190,8 @@ impl TestSucessiveInterface {
             write_count: AtomicUsize::new(0),
             should_write_addr: Vec::new(),
             should_write_data: Vec::new(),
+            interrupt_count: AtomicUsize::new(0),
+            interrupts: Vec::new(),
         }
     }
 }
@@ -187,7 +217,7 @@ impl Interface for TestSucessiveInterface {
         self.return_count.fetch_add(1, Ordering::Relaxed);
         res
     }
-    fn read_fs_string(&self, path: &PathBuf) -> Result<String, ()> {
+    fn read_fs_string(&self, path: &Path) -> Result<String, ()> {
         let res = match self.string_files[self.str_file_count.load(Ordering::Relaxed)].get(path) {
             Some(v) => Ok(v.clone()),
             None => Err(())
@@ -195,7 +225,7 @@ impl Interface for TestSucessiveInterface {
         self.str_file_count.fetch_add(1, Ordering::Relaxed);
         res
     }
-    fn read_fs_bytes(&self, path: &PathBuf) -> Result<Vec<u8>, ()> {
+    fn read_fs_bytes(&self, path: &Path) -> Result<Vec<u8>, ()> {
         let res = match self.bin_files[self.bin_file_count.load(Ordering::Relaxed)].get(path) {
             Some(v) => Ok(v.clone()),
             None => Err(())
@@ -203,10 +233,54 @@ impl Interface for TestSucessiveInterface {
         self.bin_file_count.fetch_add(1, Ordering::Relaxed);
         res
     }
-    fn write_fs_bytes(&self, data: Vec<u8>, out: &PathBuf) -> Result<(), ()> {
+    fn write_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
         assert_eq!(self.should_write_addr[self.write_count.load(Ordering::Relaxed)], *out);
         assert_eq!(self.should_write_data[self.write_count.load(Ordering::Relaxed)], data);
         self.write_count.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
+    fn append_fs_bytes(&self, data: Vec<u8>, out: &Path) -> Result<(), ()> {
+        self.write_fs_bytes(data, out)
+    }
+    fn sleep(&self, _duration: Duration) {
+        
+    }
+    fn watch_interrupts(&self) {
+        
+    }
+    fn interrupted(&self) -> bool {
+        let res = self.interrupts.get(self.interrupt_count.load(Ordering::Relaxed)).cloned().unwrap_or(false);
+        self.interrupt_count.fetch_add(1, Ordering::Relaxed);
+        res
+    }
+}
+
+pub fn default_run() -> Run {
+    Run {
+        src: PathBuf::from("foo"),
+        exe_from: ExecuteFrom::Bin,
+        og_notation: false,
+        output_model: false,
+        debug_on_err: true,
+        output_addr: vec![5],
+        output_regs: vec![Registers::Accumulator],
+        break_addr: vec![6],
+        max_steps: None,
+        detect_loops: false,
+        trace: None,
+        trace_format: TraceFormat::Text,
+        history: 1000,
+        profile: false,
+        realtime: false,
+        speed: None,
+        display: false,
+        tui: false,
+        partial: false,
+        endian: Endianness::Big,
+        load_addr: 0,
+        overlay: vec![],
+        init_acc: None,
+        entry: None,
+        poke: vec![],
+    }
 }